@@ -0,0 +1,380 @@
+//! Annealed importance sampling.
+//!
+//! Annealed importance sampling (AIS) turns annealing runs into unbiased
+//! estimates of partition function ratios. Each chain starts from a sample of
+//! the Boltzmann distribution at the schedule's initial temperature and is
+//! annealed through the schedule, accumulating an importance weight; the mean
+//! weight estimates Z(T_final) / Z(T_initial).
+
+use crate::core::energy::Energy;
+use crate::core::schedule::Schedule;
+use crate::core::state::MoveState;
+use crate::core::transition;
+use crate::rng::seeded_rng::derived_rng;
+use std::fmt;
+use std::thread;
+
+/// Results from an annealed importance sampling run.
+#[derive(Clone)]
+pub struct AisResult<S: MoveState> {
+    /// The temperatures the chains were annealed through, starting with the
+    /// initial temperature
+    pub temperatures: Vec<f64>,
+    /// The logarithm of the importance weight of each chain
+    pub log_weights: Vec<f64>,
+    /// The final state of each chain
+    pub states: Vec<S>,
+    /// The energy of the final state of each chain
+    pub energies: Vec<f64>,
+    /// The fraction of accepted Metropolis moves across all chains
+    pub acceptance_rate: f64,
+}
+
+impl<S: MoveState> AisResult<S> {
+    /// Returns the estimate of ln(Z(T_final) / Z(T_initial)), the logarithm
+    /// of the mean importance weight.
+    pub fn log_partition_ratio(&self) -> f64 {
+        let max = self.max_log_weight();
+        let mean = self
+            .log_weights
+            .iter()
+            .map(|w| (w - max).exp())
+            .sum::<f64>()
+            / self.log_weights.len() as f64;
+        max + mean.ln()
+    }
+
+    /// Returns the estimate of ln Z at the final temperature.
+    ///
+    /// # Parameters
+    ///
+    /// * `log_z_initial`: The logarithm of the partition function at the initial temperature
+    pub fn log_partition(&self, log_z_initial: f64) -> f64 {
+        log_z_initial + self.log_partition_ratio()
+    }
+
+    /// Returns the estimate of the free energy F = -T ln Z at the final temperature.
+    ///
+    /// # Parameters
+    ///
+    /// * `log_z_initial`: The logarithm of the partition function at the initial temperature
+    pub fn free_energy(&self, log_z_initial: f64) -> f64 {
+        let final_temperature = *self.temperatures.last().unwrap();
+        -final_temperature * self.log_partition(log_z_initial)
+    }
+
+    /// Returns the effective sample size of the importance weights,
+    /// (sum w)^2 / sum w^2.
+    ///
+    /// Values much smaller than the number of chains mean that a few chains
+    /// dominate the estimate, and that it is unreliable; annealing more
+    /// slowly or running more chains helps.
+    pub fn effective_sample_size(&self) -> f64 {
+        let max = self.max_log_weight();
+        let (sum, sum_squares) = self
+            .log_weights
+            .iter()
+            .map(|w| (w - max).exp())
+            .fold((0.0, 0.0), |(sum, sum_squares), w| {
+                (sum + w, sum_squares + w * w)
+            });
+        sum * sum / sum_squares
+    }
+
+    /// Returns the variance of the normalized importance weights w / mean(w).
+    ///
+    /// It is zero when all chains carry the same weight, and equals
+    /// n / ESS - 1 for n chains.
+    pub fn weight_variance(&self) -> f64 {
+        let chains = self.log_weights.len() as f64;
+        chains / self.effective_sample_size() - 1.0
+    }
+
+    /// Returns an estimate of the standard error of `log_partition_ratio`,
+    /// sqrt(weight variance / number of chains).
+    ///
+    /// The estimate comes from the weights themselves and is optimistic when
+    /// the weight distribution is heavy tailed; check the effective sample
+    /// size as well.
+    pub fn log_ratio_std_error(&self) -> f64 {
+        (self.weight_variance() / self.log_weights.len() as f64).sqrt()
+    }
+
+    fn max_log_weight(&self) -> f64 {
+        self.log_weights
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+impl<S: MoveState> fmt::Debug for AisResult<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AisResult")
+            .field("chains", &self.log_weights.len())
+            .field("temperatures", &self.temperatures.len())
+            .field("log_partition_ratio", &self.log_partition_ratio())
+            .field("effective_sample_size", &self.effective_sample_size())
+            .field("acceptance_rate", &self.acceptance_rate)
+            .finish()
+    }
+}
+
+/// Annealed importance sampling runner.
+///
+/// The schedule is evaluated once up front: starting at `initial_temp()`,
+/// `next_temp` yields the temperature of each of the `steps` annealing steps.
+/// The schedule receives no feedback, since every chain must follow the same
+/// temperatures. The temperatures need not decrease, so the runner also
+/// estimates ratios towards higher temperatures.
+///
+/// Each chain starts from one of the initial states, which must be samples of
+/// the Boltzmann distribution at the initial temperature (for example drawn
+/// with a `MetropolisSampler`). At step k, moving from T_{k-1} to T_k, the
+/// chain's log weight grows by -(1/T_k - 1/T_{k-1}) * E(x), and the state then
+/// runs `sweeps` Metropolis steps at T_k. The mean of the weights estimates
+/// Z(T_final) / Z(T_initial).
+///
+/// Chain `i` draws from `derived_rng(seed, i)`, so results are identical for
+/// a given seed regardless of the number of threads.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Point(f64);
+///
+/// impl State for Point {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         Point(self.0 + rng.gen_range(-1.0..1.0))
+///     }
+/// }
+///
+/// // A harmonic well, for which Z(T) is proportional to sqrt(T)
+/// struct Harmonic;
+///
+/// impl Energy for Harmonic {
+///     type State = Point;
+///
+///     fn cost(&self, state: &Point) -> f64 {
+///         0.5 * state.0 * state.0
+///     }
+/// }
+///
+/// // Draw the initial samples at the schedule's initial temperature
+/// let mut sampler = MetropolisSampler::new(Point(0.0), Harmonic, 4.0, seeded_rng(1))
+///     .with_burn_in(1000)
+///     .with_thinning(20);
+/// let initial = sampler.sample(200).values;
+///
+/// let result = AnnealedImportanceSampling::new(
+///     initial,
+///     Harmonic,
+///     GeometricSchedule::new(4.0, 0.95),
+///     50,
+///     42,
+/// )
+/// .run();
+///
+/// // ln(Z(T_final) / Z(T_initial)) = ln(T_final / T_initial) / 2
+/// let t_final = result.temperatures.last().unwrap();
+/// let exact = 0.5 * (t_final / 4.0).ln();
+/// assert!((result.log_partition_ratio() - exact).abs() < 0.1);
+/// println!(
+///     "ln Z ratio = {:.3} +/- {:.3} (ESS {:.1})",
+///     result.log_partition_ratio(),
+///     result.log_ratio_std_error(),
+///     result.effective_sample_size()
+/// );
+/// ```
+pub struct AnnealedImportanceSampling<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S> + Sync,
+{
+    initial_states: Vec<S>,
+    energy: E,
+    temperatures: Vec<f64>,
+    seed: u64,
+    sweeps: usize,
+    threads: usize,
+}
+
+impl<S, E> AnnealedImportanceSampling<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S> + Sync,
+{
+    /// Creates a new annealed importance sampling runner.
+    ///
+    /// By default every chain runs 10 Metropolis steps per temperature,
+    /// using as many threads as the machine provides.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_states`: Samples at the initial temperature, one per chain
+    /// * `energy`: The energy function defining the distributions
+    /// * `schedule`: The schedule whose temperatures the chains follow
+    /// * `steps`: The number of annealing steps after the initial temperature (must be positive)
+    /// * `seed`: The base seed from which all random streams are derived
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_states` is empty, `steps` is zero, or the schedule
+    /// yields a temperature that is not positive.
+    pub fn new(
+        initial_states: Vec<S>,
+        energy: E,
+        schedule: impl Schedule,
+        steps: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(
+            !initial_states.is_empty(),
+            "At least one initial state is required"
+        );
+        assert!(steps > 0, "Step count must be positive");
+
+        let mut temperatures = Vec::with_capacity(steps + 1);
+        temperatures.push(schedule.initial_temp());
+        for k in 0..steps {
+            temperatures.push(schedule.next_temp(temperatures[k], k));
+        }
+        assert!(
+            temperatures.iter().all(|&t| t > 0.0),
+            "Temperatures must be positive"
+        );
+
+        let threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Self {
+            initial_states,
+            energy,
+            temperatures,
+            seed,
+            sweeps: 10,
+            threads,
+        }
+    }
+
+    /// Sets the number of Metropolis steps each chain runs at every temperature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sweeps` is zero.
+    pub fn with_sweeps(mut self, sweeps: usize) -> Self {
+        assert!(sweeps > 0, "Sweep count must be positive");
+        self.sweeps = sweeps;
+        self
+    }
+
+    /// Sets the number of threads used to run the chains.
+    ///
+    /// The results do not depend on this setting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "Thread count must be positive");
+        self.threads = threads;
+        self
+    }
+
+    /// Returns the temperatures the chains are annealed through.
+    pub fn temperatures(&self) -> &[f64] {
+        &self.temperatures
+    }
+
+    /// Runs every chain through the schedule.
+    ///
+    /// # Returns
+    ///
+    /// An `AisResult` with the importance weights and final state of each chain.
+    pub fn run(&self) -> AisResult<S> {
+        let chains = self.initial_states.len();
+        let mut outcomes: Vec<Option<Chain<S>>> = vec![None; chains];
+
+        if self.threads == 1 || chains == 1 {
+            for (i, outcome) in outcomes.iter_mut().enumerate() {
+                *outcome = Some(self.run_chain(i));
+            }
+        } else {
+            let chunk_size = chains.div_ceil(self.threads);
+            thread::scope(|scope| {
+                for (c, chunk) in outcomes.chunks_mut(chunk_size).enumerate() {
+                    scope.spawn(move || {
+                        for (i, outcome) in chunk.iter_mut().enumerate() {
+                            *outcome = Some(self.run_chain(c * chunk_size + i));
+                        }
+                    });
+                }
+            });
+        }
+
+        let mut log_weights = Vec::with_capacity(chains);
+        let mut states = Vec::with_capacity(chains);
+        let mut energies = Vec::with_capacity(chains);
+        let mut accepted = 0;
+        for chain in outcomes.into_iter().flatten() {
+            log_weights.push(chain.log_weight);
+            states.push(chain.state);
+            energies.push(chain.energy);
+            accepted += chain.accepted;
+        }
+
+        let moves = chains * (self.temperatures.len() - 1) * self.sweeps;
+        AisResult {
+            temperatures: self.temperatures.clone(),
+            log_weights,
+            states,
+            energies,
+            acceptance_rate: accepted as f64 / moves as f64,
+        }
+    }
+
+    /// Anneals one chain, accumulating its importance weight.
+    fn run_chain(&self, index: usize) -> Chain<S> {
+        let mut rng = derived_rng(self.seed, index as u64);
+        let mut state = self.initial_states[index].clone();
+        let mut energy = self.energy.cost(&state);
+        let mut log_weight = 0.0;
+        let mut accepted = 0;
+
+        for pair in self.temperatures.windows(2) {
+            log_weight -= (1.0 / pair[1] - 1.0 / pair[0]) * energy;
+            for _ in 0..self.sweeps {
+                if transition::metropolis_step(
+                    &self.energy,
+                    &mut state,
+                    &mut energy,
+                    pair[1],
+                    &mut rng,
+                ) {
+                    accepted += 1;
+                }
+            }
+        }
+
+        Chain {
+            state,
+            energy,
+            log_weight,
+            accepted,
+        }
+    }
+}
+
+/// The outcome of one annealing chain.
+#[derive(Clone)]
+struct Chain<S> {
+    state: S,
+    energy: f64,
+    log_weight: f64,
+    accepted: usize,
+}
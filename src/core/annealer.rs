@@ -2,16 +2,28 @@
 //!
 //! This module provides the core annealing algorithm that drives the optimization process.
 
+use crate::core::checkpoint::{self, Persist};
 use crate::core::energy::Energy;
-use crate::core::schedule::Schedule;
-use crate::core::state::State;
-use crate::core::transition;
+use crate::core::equilibrium::{Epoch, Equilibrium, LevelStats};
+use crate::core::observer::{IterationRecord, Observer};
+use crate::core::reheat::ReheatPolicy;
+use crate::core::schedule::{Feedback, Schedule};
+use crate::core::state::MoveState;
+use crate::core::tabu::{TabuList, TabuMemory};
+use crate::core::termination::{FrozenDetection, FrozenTracker, Progress, StopReason, Termination};
+use crate::core::transition::{AcceptanceRule, Metropolis};
 use rand::rngs::StdRng;
+use rand::Rng;
 use std::fmt;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 /// Results from an annealing run, containing detailed statistics and the best solution found.
 #[derive(Clone)]
-pub struct AnnealingResult<S: State> {
+pub struct AnnealingResult<S: MoveState> {
     /// The best state found during the annealing process
     pub best_state: S,
     /// The energy (cost) of the best state
@@ -22,41 +34,119 @@ pub struct AnnealingResult<S: State> {
     pub final_energy: f64,
     /// The number of iterations performed
     pub iterations: usize,
+    /// The number of energy evaluations performed (including the initial state)
+    pub evaluations: usize,
     /// The number of accepted moves
     pub accepted_moves: usize,
     /// The number of rejected moves
     pub rejected_moves: usize,
+    /// The number of proposals rejected because they were tabu (included in `rejected_moves`)
+    pub tabu_rejections: usize,
     /// The initial temperature
     pub initial_temp: f64,
     /// The final temperature
     pub final_temp: f64,
+    /// The number of reheats performed
+    pub reheats: usize,
+    /// The reason the run stopped
+    pub stop_reason: StopReason,
+    /// Statistics for each temperature level, recorded when equilibrium
+    /// levels are enabled with `Annealer::with_equilibrium` (empty otherwise)
+    pub levels: Vec<LevelStats>,
+    /// The iteration at which the run froze, if it stopped with `StopReason::Frozen`
+    pub frozen_at: Option<usize>,
 }
 
-impl<S: State> fmt::Debug for AnnealingResult<S> {
+impl<S: MoveState> fmt::Debug for AnnealingResult<S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("AnnealingResult")
             .field("best_energy", &self.best_energy)
             .field("final_energy", &self.final_energy)
             .field("iterations", &self.iterations)
+            .field("evaluations", &self.evaluations)
             .field("accepted_moves", &self.accepted_moves)
             .field("rejected_moves", &self.rejected_moves)
+            .field("tabu_rejections", &self.tabu_rejections)
             .field(
                 "acceptance_ratio",
                 &(self.accepted_moves as f64 / self.iterations as f64),
             )
             .field("initial_temp", &self.initial_temp)
             .field("final_temp", &self.final_temp)
+            .field("reheats", &self.reheats)
+            .field("stop_reason", &self.stop_reason)
             .finish()
     }
 }
 
+/// Loop state of a run in progress, kept between iterations.
+#[derive(Clone, Debug)]
+struct RunState {
+    /// The schedule's initial temperature, used for reheats
+    initial_temp: f64,
+    /// The temperature for the next iteration
+    temperature: f64,
+    /// The energy of the current state
+    current_energy: f64,
+    /// The number of completed iterations
+    iteration: usize,
+    /// The number of energy evaluations performed
+    evaluations: usize,
+    /// The iteration count at which the best energy last improved
+    last_improvement: usize,
+    /// The iteration count at which the current cooling cycle started
+    cycle_start: usize,
+    /// The number of reheats performed
+    reheats: usize,
+    /// The number of temperature levels completed in the current cooling cycle
+    cycle_levels: usize,
+    /// The temperature level in progress, when equilibrium levels are enabled
+    epoch: Epoch,
+    /// Statistics of the completed temperature levels
+    levels: Vec<LevelStats>,
+    /// The state of frozen-state detection
+    frozen: FrozenTracker,
+    /// The iteration at which the run froze
+    frozen_at: Option<usize>,
+    /// Time spent in earlier sessions of a resumed run
+    elapsed: Duration,
+    /// When the current session started
+    started: Instant,
+    /// The reason the run stopped, once a termination criterion is satisfied
+    stop_reason: Option<StopReason>,
+    /// Whether the run was loaded from a checkpoint and has not continued yet
+    resumed: bool,
+}
+
+impl RunState {
+    /// Returns the wall-clock time spent on the run, across all sessions.
+    fn elapsed(&self) -> Duration {
+        self.elapsed + self.started.elapsed()
+    }
+}
+
+/// Writes a value into a checkpoint.
+///
+/// Stored as a function pointer so that the annealing loop does not require
+/// the state and generator to implement `Persist` unless checkpointing is
+/// enabled.
+type Writer<T> = fn(&T, &mut dyn Write) -> io::Result<()>;
+
+/// Where and how often checkpoints are written.
+struct Checkpointing<S, R> {
+    path: PathBuf,
+    interval: usize,
+    write_state: Writer<S>,
+    write_rng: Writer<R>,
+}
+
 /// Main annealer engine that performs simulated annealing optimization.
 ///
 /// The `Annealer` encapsulates all components needed for simulated annealing:
-/// - A state representation
+/// - A state representation (any `State`, or a `MoveState` mutated in place)
 /// - An energy function to be minimized
 /// - A cooling schedule
-/// - A random number generator
+/// - A random number generator, `StdRng` unless another one is given
 /// - Termination criteria
 ///
 /// # Examples
@@ -103,11 +193,13 @@ impl<S: State> fmt::Debug for AnnealingResult<S> {
 ///
 /// let (best_state, best_energy) = annealer.run();
 /// ```
-pub struct Annealer<S, E, Sch>
+pub struct Annealer<S, E, Sch, A = Metropolis, R = StdRng>
 where
-    S: State,
+    S: MoveState,
     E: Energy<State = S>,
     Sch: Schedule,
+    A: AcceptanceRule,
+    R: Rng,
 {
     /// The current state in the annealing process
     pub state: S,
@@ -115,9 +207,11 @@ where
     pub energy: E,
     /// The cooling schedule
     pub schedule: Sch,
+    /// The rule deciding whether proposed moves are accepted
+    pub acceptance: A,
     /// The random number generator
-    pub rng: StdRng,
-    /// The maximum number of iterations
+    pub rng: R,
+    /// The maximum number of iterations, applied even when other termination criteria are set
     pub max_iters: usize,
     /// The best state found so far
     best_state: Option<S>,
@@ -129,13 +223,32 @@ where
     accepted_moves: usize,
     /// Number of rejected moves
     rejected_moves: usize,
+    /// Number of proposals rejected as tabu
+    tabu_rejections: usize,
+    /// Observers notified of progress during the run
+    observers: Vec<Box<dyn Observer<S>>>,
+    /// Additional stopping conditions, checked before the first and after every iteration
+    termination: Vec<Box<dyn Termination>>,
+    /// Optional policy for raising the temperature again
+    reheat: Option<ReheatPolicy>,
+    /// Optional criterion ending each temperature level
+    equilibrium: Option<Equilibrium>,
+    /// Optional detection of the frozen regime
+    frozen_detection: Option<FrozenDetection>,
+    /// Optional memory of recent moves that may not be repeated
+    tabu: Option<Box<dyn TabuMemory<S>>>,
+    /// Optional periodic checkpointing
+    checkpointing: Option<Checkpointing<S, R>>,
+    /// Loop state of the run in progress, started by `step` or loaded from a checkpoint
+    run: Option<RunState>,
 }
 
-impl<S, E, Sch> Annealer<S, E, Sch>
+impl<S, E, Sch, R> Annealer<S, E, Sch, Metropolis, R>
 where
-    S: State,
+    S: MoveState,
     E: Energy<State = S>,
     Sch: Schedule,
+    R: Rng,
 {
     /// Creates a new annealer with the given components.
     ///
@@ -172,12 +285,13 @@ where
     ///     10000,
     /// );
     /// ```
-    pub fn new(initial_state: S, energy: E, schedule: Sch, rng: StdRng, max_iters: usize) -> Self {
+    pub fn new(initial_state: S, energy: E, schedule: Sch, rng: R, max_iters: usize) -> Self {
         let initial_energy = energy.cost(&initial_state);
         Self {
             state: initial_state,
             energy,
             schedule,
+            acceptance: Metropolis,
             rng,
             max_iters,
             best_state: None,
@@ -185,9 +299,27 @@ where
             collect_stats: false,
             accepted_moves: 0,
             rejected_moves: 0,
+            tabu_rejections: 0,
+            observers: Vec::new(),
+            termination: Vec::new(),
+            reheat: None,
+            equilibrium: None,
+            frozen_detection: None,
+            tabu: None,
+            checkpointing: None,
+            run: None,
         }
     }
+}
 
+impl<S, E, Sch, A, R> Annealer<S, E, Sch, A, R>
+where
+    S: MoveState,
+    E: Energy<State = S>,
+    Sch: Schedule,
+    A: AcceptanceRule,
+    R: Rng,
+{
     /// Enables collection of detailed statistics during the annealing process.
     ///
     /// When enabled, the annealer will track additional information such as
@@ -202,10 +334,241 @@ where
         self
     }
 
+    /// Registers an observer that is notified of progress during the run.
+    ///
+    /// Observers are invoked in the order they were registered. See `Observer`
+    /// for the available callbacks.
+    ///
+    /// # Parameters
+    ///
+    /// * `observer`: The observer to register
+    ///
+    /// # Returns
+    ///
+    /// The modified annealer with the observer registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::observer::ProgressLogger;
+    /// use frostfire::prelude::*;
+    ///
+    /// # #[derive(Clone)]
+    /// # struct MyState;
+    /// # impl State for MyState {
+    /// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+    /// # }
+    /// # struct MyEnergy;
+    /// # impl Energy for MyEnergy {
+    /// #     type State = MyState;
+    /// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+    /// # }
+    /// let annealer = Annealer::new(
+    ///     MyState,
+    ///     MyEnergy,
+    ///     GeometricSchedule::new(100.0, 0.95),
+    ///     seeded_rng(42),
+    ///     10000,
+    /// )
+    /// .with_observer(ProgressLogger::new(1000));
+    /// ```
+    pub fn with_observer(mut self, observer: impl Observer<S> + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Adds a termination criterion that can stop the run before `max_iters`.
+    ///
+    /// Criteria are checked before the first iteration and after every
+    /// iteration, and the run stops as soon as any of them is satisfied. The triggering reason is reported in
+    /// `AnnealingResult::stop_reason`.
+    ///
+    /// # Parameters
+    ///
+    /// * `criterion`: The stopping condition to add
+    ///
+    /// # Returns
+    ///
+    /// The modified annealer with the criterion registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::termination::{NoImprovement, StopReason, TargetEnergy};
+    /// use frostfire::prelude::*;
+    ///
+    /// # #[derive(Clone)]
+    /// # struct MyState;
+    /// # impl State for MyState {
+    /// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+    /// # }
+    /// # struct MyEnergy;
+    /// # impl Energy for MyEnergy {
+    /// #     type State = MyState;
+    /// #     fn cost(&self, _: &Self::State) -> f64 { 1.0 }
+    /// # }
+    /// let mut annealer = Annealer::new(
+    ///     MyState,
+    ///     MyEnergy,
+    ///     GeometricSchedule::new(100.0, 0.95),
+    ///     seeded_rng(42),
+    ///     10000,
+    /// )
+    /// .with_termination(TargetEnergy::new(0.0).or(NoImprovement::new(500)));
+    ///
+    /// let result = annealer.run_with_stats();
+    /// assert_eq!(result.stop_reason, StopReason::Stagnation);
+    /// assert_eq!(result.iterations, 500);
+    /// ```
+    pub fn with_termination(mut self, criterion: impl Termination + 'static) -> Self {
+        self.termination.push(Box::new(criterion));
+        self
+    }
+
+    /// Sets the policy used to reheat the system during the run.
+    ///
+    /// See `ReheatPolicy` for the available strategies. Reheats are never
+    /// triggered on the final iteration.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy`: The reheating policy
+    ///
+    /// # Returns
+    ///
+    /// The modified annealer with the reheating policy set.
+    pub fn with_reheat(mut self, policy: ReheatPolicy) -> Self {
+        self.reheat = Some(policy);
+        self
+    }
+
+    /// Runs each temperature for a whole level instead of a single iteration.
+    ///
+    /// Each level continues until the equilibrium criterion is met or the
+    /// level reaches its maximum length; only then is the schedule asked for
+    /// the next temperature. Statistics for every level, including the final
+    /// incomplete one, are reported in `AnnealingResult::levels`. See
+    /// `Equilibrium` for the available criteria.
+    ///
+    /// # Parameters
+    ///
+    /// * `equilibrium`: The criterion ending each temperature level
+    ///
+    /// # Returns
+    ///
+    /// The modified annealer with equilibrium levels enabled.
+    pub fn with_equilibrium(mut self, equilibrium: Equilibrium) -> Self {
+        self.equilibrium = Some(equilibrium);
+        self
+    }
+
+    /// Stops the run early once the search is frozen.
+    ///
+    /// See `FrozenDetection` for how the frozen regime is detected. The run
+    /// stops with `StopReason::Frozen` and reports the iteration at which it
+    /// froze in `AnnealingResult::frozen_at`.
+    ///
+    /// # Parameters
+    ///
+    /// * `detection`: The frozen-state detector
+    ///
+    /// # Returns
+    ///
+    /// The modified annealer with frozen-state detection enabled.
+    pub fn with_frozen_detection(mut self, detection: FrozenDetection) -> Self {
+        self.frozen_detection = Some(detection);
+        self
+    }
+
+    /// Adds a tabu memory that rejects proposals repeating recent moves.
+    ///
+    /// See `TabuList` for how attributes, tenure and aspiration work. The
+    /// memory is cleared when a new run starts, and is saved in checkpoints.
+    ///
+    /// # Parameters
+    ///
+    /// * `tabu`: The tabu list to use
+    ///
+    /// # Returns
+    ///
+    /// The modified annealer with the tabu memory enabled.
+    pub fn with_tabu<T>(mut self, tabu: TabuList<S, T>) -> Self
+    where
+        S: 'static,
+        T: Eq + Hash + Send + Persist + 'static,
+    {
+        self.tabu = Some(Box::new(tabu));
+        self
+    }
+
+    /// Replaces the rule deciding whether proposed moves are accepted.
+    ///
+    /// The default rule is `Metropolis`. See `AcceptanceRule` for the
+    /// available rules.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule`: The acceptance rule to use
+    ///
+    /// # Returns
+    ///
+    /// The annealer using the new acceptance rule, with all other settings kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::transition::Barker;
+    /// use frostfire::prelude::*;
+    ///
+    /// # #[derive(Clone)]
+    /// # struct MyState;
+    /// # impl State for MyState {
+    /// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+    /// # }
+    /// # struct MyEnergy;
+    /// # impl Energy for MyEnergy {
+    /// #     type State = MyState;
+    /// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+    /// # }
+    /// let annealer = Annealer::new(
+    ///     MyState,
+    ///     MyEnergy,
+    ///     GeometricSchedule::new(100.0, 0.95),
+    ///     seeded_rng(42),
+    ///     10000,
+    /// )
+    /// .with_acceptance(Barker);
+    /// ```
+    pub fn with_acceptance<B: AcceptanceRule>(self, rule: B) -> Annealer<S, E, Sch, B, R> {
+        Annealer {
+            state: self.state,
+            energy: self.energy,
+            schedule: self.schedule,
+            acceptance: rule,
+            rng: self.rng,
+            max_iters: self.max_iters,
+            best_state: self.best_state,
+            best_energy: self.best_energy,
+            collect_stats: self.collect_stats,
+            accepted_moves: self.accepted_moves,
+            rejected_moves: self.rejected_moves,
+            tabu_rejections: self.tabu_rejections,
+            observers: self.observers,
+            termination: self.termination,
+            reheat: self.reheat,
+            equilibrium: self.equilibrium,
+            frozen_detection: self.frozen_detection,
+            tabu: self.tabu,
+            checkpointing: self.checkpointing,
+            run: self.run,
+        }
+    }
+
     /// Runs the annealing process to completion.
     ///
     /// This method performs the simulated annealing algorithm until the
-    /// maximum number of iterations is reached.
+    /// maximum number of iterations is reached or a termination criterion
+    /// is satisfied.
     ///
     /// # Returns
     ///
@@ -283,10 +646,157 @@ where
     /// println!("Acceptance ratio: {}", result.accepted_moves as f64 / result.iterations as f64);
     /// ```
     pub fn run_with_stats(&mut self) -> AnnealingResult<S> {
-        // Initialize variables
+        // Continue a run in progress, or start a fresh one
+        let mut run = self.take_run();
+
+        while run.stop_reason.is_none() && run.iteration < self.max_iters {
+            self.iterate(&mut run);
+        }
+
+        self.finish_run(run)
+    }
+
+    /// Performs a single iteration of the run in progress.
+    ///
+    /// The first call starts a new run. Each call proposes one move, decides
+    /// whether to accept it and updates the temperature, exactly as one
+    /// iteration of `run_with_stats`. Once `max_iters` is reached or a
+    /// termination criterion is satisfied, `step` returns `None`; call
+    /// `finish` to collect the result and end the run. Stepping can be mixed
+    /// with `run_with_stats`, which continues the run in progress.
+    ///
+    /// # Returns
+    ///
+    /// A record of the iteration just performed, or `None` if the run is over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::prelude::*;
+    ///
+    /// # #[derive(Clone)]
+    /// # struct MyState;
+    /// # impl State for MyState {
+    /// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+    /// # }
+    /// # struct MyEnergy;
+    /// # impl Energy for MyEnergy {
+    /// #     type State = MyState;
+    /// #     fn cost(&self, _: &Self::State) -> f64 { 1.0 }
+    /// # }
+    /// let mut annealer = Annealer::new(
+    ///     MyState,
+    ///     MyEnergy,
+    ///     GeometricSchedule::new(100.0, 0.95),
+    ///     seeded_rng(42),
+    ///     10000,
+    /// );
+    ///
+    /// // Interleave annealing with other work
+    /// while let Some(record) = annealer.step() {
+    ///     if record.iteration % 1000 == 0 {
+    ///         println!("T = {:.3}, E = {}", record.temperature, record.current_energy);
+    ///     }
+    /// }
+    ///
+    /// let result = annealer.finish();
+    /// assert_eq!(result.iterations, 10000);
+    /// assert_eq!(result.stop_reason, StopReason::MaxIterations);
+    /// ```
+    pub fn step(&mut self) -> Option<IterationRecord> {
+        let mut run = self.take_run();
+
+        let record = if run.stop_reason.is_none() && run.iteration < self.max_iters {
+            Some(self.iterate(&mut run))
+        } else {
+            None
+        };
+
+        self.run = Some(run);
+        record
+    }
+
+    /// Returns an iterator that drives the run one iteration at a time.
+    ///
+    /// Each item is the record returned by `step`, and the iterator ends when
+    /// the run is over. Dropping the iterator early leaves the run in
+    /// progress, so it can be continued or finished later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::prelude::*;
+    ///
+    /// # #[derive(Clone)]
+    /// # struct MyState;
+    /// # impl State for MyState {
+    /// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+    /// # }
+    /// # struct MyEnergy;
+    /// # impl Energy for MyEnergy {
+    /// #     type State = MyState;
+    /// #     fn cost(&self, _: &Self::State) -> f64 { 1.0 }
+    /// # }
+    /// let mut annealer = Annealer::new(
+    ///     MyState,
+    ///     MyEnergy,
+    ///     GeometricSchedule::new(100.0, 0.95),
+    ///     seeded_rng(42),
+    ///     10000,
+    /// );
+    ///
+    /// // Custom stopping logic: stop once the system has cooled down
+    /// let cold = annealer.steps().position(|record| record.temperature < 1.0);
+    /// assert!(cold.is_some());
+    ///
+    /// let result = annealer.finish();
+    /// assert_eq!(result.stop_reason, StopReason::Interrupted);
+    /// ```
+    pub fn steps(&mut self) -> Steps<'_, S, E, Sch, A, R> {
+        Steps { annealer: self }
+    }
+
+    /// Ends the run in progress and returns its result.
+    ///
+    /// If the run stopped on its own, its stop reason is reported; if it is
+    /// ended before that, the stop reason is `StopReason::Interrupted`. The
+    /// next call to `step` or `run_with_stats` starts a new run from the
+    /// current state.
+    ///
+    /// # Returns
+    ///
+    /// An `AnnealingResult` for the iterations performed so far.
+    pub fn finish(&mut self) -> AnnealingResult<S> {
+        let run = self.take_run();
+        self.finish_run(run)
+    }
+
+    /// Returns the run in progress, starting a new one if there is none.
+    ///
+    /// A run loaded from a checkpoint notifies the observers that it resumes.
+    /// The termination criteria are checked before a new or resumed run
+    /// performs its first iteration.
+    fn take_run(&mut self) -> RunState {
+        let mut run = match self.run.take() {
+            Some(run) if !run.resumed => return run,
+            Some(mut run) => {
+                run.resumed = false;
+                let best_state = self.best_state.as_ref().unwrap_or(&self.state);
+                for observer in &mut self.observers {
+                    observer.on_resume(run.iteration, best_state, self.best_energy);
+                }
+                run
+            }
+            None => self.start_run(),
+        };
+        self.check_termination(&mut run);
+        run
+    }
+
+    /// Initializes the loop state and statistics for a fresh run.
+    fn start_run(&mut self) -> RunState {
         let initial_temp = self.schedule.initial_temp();
-        let mut current_temp = initial_temp;
-        let mut current_energy = self.energy.cost(&self.state);
+        let current_energy = self.energy.cost(&self.state);
 
         // Save the initial state as the best state
         self.best_state = Some(self.state.clone());
@@ -295,50 +805,558 @@ where
         // Reset statistics
         self.accepted_moves = 0;
         self.rejected_moves = 0;
+        self.tabu_rejections = 0;
+        if let Some(tabu) = &mut self.tabu {
+            tabu.clear();
+        }
 
-        // Main annealing loop
-        for i in 0..self.max_iters {
-            // Generate a neighboring state
-            let new_state = self.state.neighbor(&mut self.rng);
-            let new_energy = self.energy.cost(&new_state);
+        for observer in &mut self.observers {
+            observer.on_start(&self.state, current_energy);
+        }
+
+        RunState {
+            initial_temp,
+            temperature: initial_temp,
+            current_energy,
+            iteration: 0,
+            evaluations: 1,
+            last_improvement: 0,
+            cycle_start: 0,
+            reheats: 0,
+            cycle_levels: 0,
+            epoch: Epoch::default(),
+            levels: Vec::new(),
+            frozen: FrozenTracker::default(),
+            frozen_at: None,
+            elapsed: Duration::ZERO,
+            started: Instant::now(),
+            stop_reason: None,
+            resumed: false,
+        }
+    }
+
+    /// Performs a single annealing iteration.
+    ///
+    /// Sets the run's stop reason if a termination criterion is satisfied.
+    ///
+    /// # Returns
+    ///
+    /// A record of the iteration.
+    fn iterate(&mut self, run: &mut RunState) -> IterationRecord {
+        let i = run.iteration;
+        let current_temp = run.temperature;
+
+        // Propose a move without applying it
+        let mut mv = self.state.propose_move(&mut self.rng);
+        let tabu = match &mut self.tabu {
+            Some(tabu) => tabu.check(&mut self.state, &mut mv, i),
+            None => false,
+        };
+
+        // Tabu moves are rejected outright, unless aspiration lets through those
+        // yielding a new best. Without aspiration they are not even evaluated.
+        let aspiration = self.tabu.as_ref().is_some_and(|tabu| tabu.aspiration());
+        let (accepted, delta) = if tabu && !aspiration {
+            self.tabu_rejections += 1;
+            (false, f64::NAN)
+        } else {
+            // Calculate the energy difference, incrementally if the energy supports it
+            let delta = self
+                .energy
+                .delta(&mut self.state, &mut mv, run.current_energy);
+            run.evaluations += 1;
 
-            // Calculate the energy difference
-            let delta = new_energy - current_energy;
+            let new_best = run.current_energy + delta < self.best_energy;
+            if tabu && !new_best {
+                self.tabu_rejections += 1;
+                (false, delta)
+            } else {
+                let accepted = self.acceptance.accept(delta, current_temp, &mut self.rng);
+                (accepted, delta)
+            }
+        };
+        let new_energy = run.current_energy + delta;
+        if accepted {
+            // Accept the move by applying it in place
+            self.state.apply_move(&mut mv);
+            run.current_energy = new_energy;
+            if let Some(tabu) = &mut self.tabu {
+                tabu.record(i);
+            }
 
-            // Decide whether to accept the new state
-            if transition::accept(delta, current_temp, &mut self.rng) {
-                // Accept the new state
-                self.state = new_state;
-                current_energy = new_energy;
+            // Update statistics
+            self.accepted_moves += 1;
 
-                // Update statistics
-                self.accepted_moves += 1;
+            for observer in &mut self.observers {
+                observer.on_accept(i, &self.state, new_energy);
+            }
+
+            // Update the best state if we found a better one, reusing its allocation
+            if new_energy < self.best_energy {
+                match self.best_state.as_mut() {
+                    Some(best_state) => best_state.clone_from(&self.state),
+                    None => self.best_state = Some(self.state.clone()),
+                }
+                self.best_energy = new_energy;
+                run.last_improvement = i + 1;
 
-                // Update the best state if we found a better one
-                if new_energy < self.best_energy {
-                    self.best_state = Some(self.state.clone());
-                    self.best_energy = new_energy;
+                for observer in &mut self.observers {
+                    observer.on_new_best(i, &self.state, new_energy);
                 }
+            }
+        } else {
+            // Reject the new state
+            self.rejected_moves += 1;
+        }
+
+        let record = IterationRecord {
+            iteration: i,
+            temperature: current_temp,
+            current_energy: run.current_energy,
+            best_energy: self.best_energy,
+            accepted,
+        };
+        for observer in &mut self.observers {
+            observer.on_iteration(&record);
+        }
+
+        // Report the outcome to the schedule, then update the temperature.
+        // The schedule's iteration counter restarts after every reheat; with
+        // equilibrium levels it counts levels rather than iterations.
+        let schedule_iteration = match self.equilibrium {
+            Some(_) => run.cycle_levels,
+            None => i - run.cycle_start,
+        };
+        self.schedule.observe(&Feedback {
+            iteration: schedule_iteration,
+            temperature: current_temp,
+            accepted,
+            delta,
+            current_energy: run.current_energy,
+            best_energy: self.best_energy,
+        });
+        let mut level_ended = false;
+        let mut next_temp = match &self.equilibrium {
+            Some(equilibrium) => {
+                match run.epoch.record(equilibrium, accepted, run.current_energy) {
+                    Some(equilibrated) => {
+                        Self::end_level(run, current_temp, self.best_energy, equilibrated);
+                        level_ended = true;
+                        run.cycle_levels += 1;
+                        self.schedule.next_temp(current_temp, schedule_iteration)
+                    }
+                    None => current_temp,
+                }
+            }
+            None => self.schedule.next_temp(current_temp, schedule_iteration),
+        };
+        run.iteration = i + 1;
+        let iterations = run.iteration;
+
+        // Reheat if the policy calls for it and the run is not over
+        let mut reheated = false;
+        if let Some(policy) = &self.reheat {
+            let stagnant = iterations - run.last_improvement.max(run.cycle_start);
+            if iterations < self.max_iters
+                && policy.is_due(iterations - run.cycle_start, stagnant, run.reheats)
+            {
+                next_temp = run.initial_temp * policy.fraction();
+                if policy.restarts_from_best() {
+                    if let Some(best_state) = &self.best_state {
+                        self.state.clone_from(best_state);
+                        run.current_energy = self.best_energy;
+                    }
+                }
+                run.cycle_start = iterations;
+                run.reheats += 1;
+                if !run.epoch.is_empty() {
+                    Self::end_level(run, current_temp, self.best_energy, false);
+                }
+                run.cycle_levels = 0;
+                reheated = true;
+            }
+        }
+
+        if next_temp != current_temp {
+            for observer in &mut self.observers {
+                observer.on_temperature_change(i, current_temp, next_temp);
+            }
+        }
+        run.temperature = next_temp;
+
+        // Detect the frozen regime at the end of each temperature level
+        if let Some(detection) = &self.frozen_detection {
+            if reheated {
+                run.frozen = FrozenTracker::default();
             } else {
-                // Reject the new state
-                self.rejected_moves += 1;
+                run.frozen.level_iterations += 1;
+                if accepted {
+                    run.frozen.level_accepted += 1;
+                }
+                let level_complete = match self.equilibrium {
+                    Some(_) => level_ended,
+                    None => run.frozen.level_iterations >= detection.level_length(),
+                };
+                if level_complete {
+                    run.frozen_at =
+                        detection.record_level(&mut run.frozen, iterations, run.last_improvement);
+                    if run.frozen_at.is_some() {
+                        run.stop_reason = Some(StopReason::Frozen);
+                        return record;
+                    }
+                }
             }
+        }
+
+        // Check the termination criteria
+        self.check_termination(run);
+        if run.stop_reason.is_some() {
+            return record;
+        }
+
+        // Write a checkpoint if one is due and the run continues
+        if let Some(checkpointing) = &self.checkpointing {
+            if iterations < self.max_iters && iterations.is_multiple_of(checkpointing.interval) {
+                if let Err(err) = self.write_checkpoint_file(run, checkpointing) {
+                    log::warn!(
+                        "Failed to write checkpoint to {}: {}",
+                        checkpointing.path.display(),
+                        err
+                    );
+                }
+            }
+        }
+
+        record
+    }
 
-            // Update the temperature according to the cooling schedule
-            current_temp = self.schedule.next_temp(current_temp, i);
+    /// Sets the run's stop reason if a termination criterion is satisfied.
+    fn check_termination(&mut self, run: &mut RunState) {
+        if self.termination.is_empty() {
+            return;
         }
+        let progress = Progress {
+            iteration: run.iteration,
+            evaluations: run.evaluations,
+            temperature: run.temperature,
+            current_energy: run.current_energy,
+            best_energy: self.best_energy,
+            iterations_since_improvement: run.iteration - run.last_improvement,
+            elapsed: run.elapsed(),
+        };
+        run.stop_reason = self
+            .termination
+            .iter_mut()
+            .find_map(|criterion| criterion.check(&progress));
+    }
+
+    /// Records the statistics of the level in progress and starts a new one.
+    fn end_level(run: &mut RunState, temperature: f64, best_energy: f64, equilibrated: bool) {
+        let epoch = std::mem::take(&mut run.epoch);
+        run.levels
+            .push(epoch.stats(temperature, best_energy, equilibrated));
+    }
 
-        // Create the result object
-        AnnealingResult {
+    /// Builds the result of a run and notifies the observers.
+    fn finish_run(&mut self, mut run: RunState) -> AnnealingResult<S> {
+        let stop_reason = match run.stop_reason.take() {
+            Some(reason) => reason,
+            None if run.iteration >= self.max_iters => StopReason::MaxIterations,
+            None => StopReason::Interrupted,
+        };
+        if !run.epoch.is_empty() {
+            let temperature = run.temperature;
+            Self::end_level(&mut run, temperature, self.best_energy, false);
+        }
+        let result = AnnealingResult {
             best_state: self.best_state.as_ref().unwrap().clone(),
             best_energy: self.best_energy,
             final_state: self.state.clone(),
-            final_energy: current_energy,
-            iterations: self.max_iters,
+            final_energy: run.current_energy,
+            iterations: run.iteration,
+            evaluations: run.evaluations,
             accepted_moves: self.accepted_moves,
             rejected_moves: self.rejected_moves,
-            initial_temp,
-            final_temp: current_temp,
+            tabu_rejections: self.tabu_rejections,
+            initial_temp: run.initial_temp,
+            final_temp: run.temperature,
+            reheats: run.reheats,
+            stop_reason,
+            levels: run.levels,
+            frozen_at: run.frozen_at,
+        };
+
+        for observer in &mut self.observers {
+            observer.on_finish(&result);
+        }
+
+        result
+    }
+
+    /// Writes a checkpoint file.
+    fn write_checkpoint_file(
+        &self,
+        run: &RunState,
+        checkpointing: &Checkpointing<S, R>,
+    ) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        self.encode_checkpoint(run, checkpointing, &mut bytes)?;
+        checkpoint::write_atomically(&checkpointing.path, &bytes)
+    }
+
+    /// Serializes the annealer and loop state.
+    fn encode_checkpoint(
+        &self,
+        run: &RunState,
+        checkpointing: &Checkpointing<S, R>,
+        writer: &mut Vec<u8>,
+    ) -> io::Result<()> {
+        let writer: &mut dyn Write = writer;
+        checkpoint::write_header(writer)?;
+
+        run.iteration.write_to(writer)?;
+        run.evaluations.write_to(writer)?;
+        run.last_improvement.write_to(writer)?;
+        run.cycle_start.write_to(writer)?;
+        run.reheats.write_to(writer)?;
+        self.accepted_moves.write_to(writer)?;
+        self.rejected_moves.write_to(writer)?;
+        self.tabu_rejections.write_to(writer)?;
+        run.initial_temp.write_to(writer)?;
+        run.temperature.write_to(writer)?;
+        run.current_energy.write_to(writer)?;
+        self.best_energy.write_to(writer)?;
+        let elapsed = run.elapsed();
+        elapsed.as_secs().write_to(writer)?;
+        elapsed.subsec_nanos().write_to(writer)?;
+
+        run.cycle_levels.write_to(writer)?;
+        run.epoch.write_to(writer)?;
+        run.levels.write_to(writer)?;
+        run.frozen.write_to(writer)?;
+
+        (checkpointing.write_rng)(&self.rng, writer)?;
+
+        let write_state = checkpointing.write_state;
+        write_state(&self.state, writer)?;
+        write_state(self.best_state.as_ref().unwrap_or(&self.state), writer)?;
+        self.schedule.save_state(writer)?;
+
+        self.tabu.is_some().write_to(writer)?;
+        match &self.tabu {
+            Some(tabu) => tabu.save_state(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<S, E, Sch, A, R> Annealer<S, E, Sch, A, R>
+where
+    S: MoveState + Persist,
+    E: Energy<State = S>,
+    Sch: Schedule,
+    A: AcceptanceRule,
+    R: Rng + Persist,
+{
+    /// Periodically writes a checkpoint from which the run can be resumed.
+    ///
+    /// Every `interval` iterations the current state, best state, temperature,
+    /// iteration counter, statistics, schedule state, random number
+    /// generator position and tabu memory are written to `path`. The file is replaced
+    /// atomically, so an interrupted write leaves the previous checkpoint
+    /// intact. Failures to write are logged and do not stop the run.
+    ///
+    /// The random number generator must implement `Persist` so that its exact
+    /// position can be saved; use `ChaCha12Rng` rather than `StdRng`, which
+    /// does not expose its position. Writing checkpoints does not consume
+    /// random numbers, so a run resumed from a checkpoint is identical to one
+    /// that never stopped, with or without checkpoints.
+    ///
+    /// Observers and termination criteria are not part of the checkpoint and
+    /// must be registered again on the resumed annealer. Instead of
+    /// `Observer::on_start`, the observers of a resumed run receive
+    /// `Observer::on_resume` with the restored best state.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: The file to write checkpoints to
+    /// * `interval`: The number of iterations between checkpoints (must be positive)
+    ///
+    /// # Returns
+    ///
+    /// The modified annealer with checkpointing enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use frostfire::core::checkpoint::Persist;
+    /// use frostfire::prelude::*;
+    /// use rand::SeedableRng;
+    /// use std::io::{self, Read, Write};
+    ///
+    /// # #[derive(Clone)]
+    /// # struct MyState(f64);
+    /// # impl State for MyState {
+    /// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+    /// # }
+    /// # impl Persist for MyState {
+    /// #     fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> { self.0.write_to(writer) }
+    /// #     fn read_from(reader: &mut dyn Read) -> io::Result<Self> { Ok(MyState(f64::read_from(reader)?)) }
+    /// # }
+    /// # struct MyEnergy;
+    /// # impl Energy for MyEnergy {
+    /// #     type State = MyState;
+    /// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+    /// # }
+    /// let path = "run.ckpt";
+    /// let mut annealer = Annealer::new(
+    ///     MyState(0.0),
+    ///     MyEnergy,
+    ///     GeometricSchedule::new(100.0, 0.9999),
+    ///     ChaCha12Rng::seed_from_u64(42),
+    ///     100_000_000,
+    /// )
+    /// .with_checkpoints(path, 1_000_000);
+    ///
+    /// // Pick up where a previous process left off
+    /// if std::path::Path::new(path).exists() {
+    ///     annealer = annealer.resume_from(path)?;
+    /// }
+    ///
+    /// let result = annealer.run_with_stats();
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn with_checkpoints(mut self, path: impl Into<PathBuf>, interval: usize) -> Self {
+        assert!(interval > 0, "Checkpoint interval must be positive");
+        self.checkpointing = Some(Checkpointing {
+            path: path.into(),
+            interval,
+            write_state: S::write_to,
+            write_rng: R::write_to,
+        });
+        self
+    }
+
+    /// Loads a checkpoint file so that the next run continues from it.
+    ///
+    /// The annealer must be configured with the same energy function,
+    /// schedule, reheating policy, equilibrium criterion, frozen-state
+    /// detection and tabu list as the run that wrote the checkpoint.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: The checkpoint file to load
+    ///
+    /// # Returns
+    ///
+    /// The annealer positioned at the checkpoint, or an error if the file
+    /// cannot be read or is not a valid checkpoint.
+    pub fn resume_from(mut self, path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        self.read_checkpoint(&mut BufReader::new(file))?;
+        Ok(self)
+    }
+
+    /// Reads a checkpoint so that the next run continues from it.
+    ///
+    /// See `resume_from` for the requirements on the annealer's configuration.
+    ///
+    /// # Parameters
+    ///
+    /// * `reader`: The source of the checkpoint data
+    pub fn read_checkpoint(&mut self, reader: &mut impl Read) -> io::Result<()> {
+        let reader: &mut dyn Read = reader;
+        checkpoint::read_header(reader)?;
+
+        let iteration = usize::read_from(reader)?;
+        let evaluations = usize::read_from(reader)?;
+        let last_improvement = usize::read_from(reader)?;
+        let cycle_start = usize::read_from(reader)?;
+        let reheats = usize::read_from(reader)?;
+        let accepted_moves = usize::read_from(reader)?;
+        let rejected_moves = usize::read_from(reader)?;
+        let tabu_rejections = usize::read_from(reader)?;
+        let initial_temp = f64::read_from(reader)?;
+        let temperature = f64::read_from(reader)?;
+        let current_energy = f64::read_from(reader)?;
+        let best_energy = f64::read_from(reader)?;
+        let elapsed = Duration::new(u64::read_from(reader)?, u32::read_from(reader)?);
+        let cycle_levels = usize::read_from(reader)?;
+        let epoch = Epoch::read_from(reader)?;
+        let levels = Vec::<LevelStats>::read_from(reader)?;
+        let frozen = FrozenTracker::read_from(reader)?;
+
+        let rng = R::read_from(reader)?;
+
+        let state = S::read_from(reader)?;
+        let best_state = S::read_from(reader)?;
+        self.schedule.load_state(reader)?;
+        if bool::read_from(reader)? != self.tabu.is_some() {
+            return Err(checkpoint::invalid_data(
+                "tabu memory does not match the checkpoint",
+            ));
         }
+        if let Some(tabu) = &mut self.tabu {
+            tabu.load_state(reader)?;
+        }
+
+        self.state = state;
+        self.best_state = Some(best_state);
+        self.best_energy = best_energy;
+        self.accepted_moves = accepted_moves;
+        self.rejected_moves = rejected_moves;
+        self.tabu_rejections = tabu_rejections;
+        self.rng = rng;
+        self.run = Some(RunState {
+            initial_temp,
+            temperature,
+            current_energy,
+            iteration,
+            evaluations,
+            last_improvement,
+            cycle_start,
+            reheats,
+            cycle_levels,
+            epoch,
+            levels,
+            frozen,
+            frozen_at: None,
+            elapsed,
+            started: Instant::now(),
+            stop_reason: None,
+            resumed: true,
+        });
+        Ok(())
+    }
+}
+
+/// Iterator over the iterations of an annealing run, created by `Annealer::steps`.
+pub struct Steps<'a, S, E, Sch, A = Metropolis, R = StdRng>
+where
+    S: MoveState,
+    E: Energy<State = S>,
+    Sch: Schedule,
+    A: AcceptanceRule,
+    R: Rng,
+{
+    annealer: &'a mut Annealer<S, E, Sch, A, R>,
+}
+
+impl<S, E, Sch, A, R> Iterator for Steps<'_, S, E, Sch, A, R>
+where
+    S: MoveState,
+    E: Energy<State = S>,
+    Sch: Schedule,
+    A: AcceptanceRule,
+    R: Rng,
+{
+    type Item = IterationRecord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.annealer.step()
     }
 }
@@ -0,0 +1,217 @@
+//! Automatic calibration of the initial temperature.
+//!
+//! The right initial temperature depends on the scale of the energy
+//! differences of the problem. This module samples a random walk from the
+//! initial state, measures the uphill energy differences, and computes a
+//! temperature at which a chosen fraction of uphill moves is accepted. The
+//! result can be passed to the constructor of any schedule.
+
+use crate::core::energy::Energy;
+use crate::core::state::MoveState;
+use rand::Rng;
+
+/// The method used to compute the initial temperature from the sampled moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalibrationMethod {
+    /// The classic estimate of Kirkpatrick et al., T0 = -mean(uphill delta) / ln(chi0).
+    ///
+    /// Fast and simple, but since it only uses the mean delta the actual
+    /// acceptance rate is somewhat higher than the target when the deltas vary.
+    Kirkpatrick,
+    /// The iterative method of Ben-Ameur (2004), which solves for the
+    /// temperature at which the sampled uphill moves are accepted with the
+    /// target probability on average. Every sampled move is weighted equally,
+    /// since the walk does not sample states from the Boltzmann distribution.
+    BenAmeur,
+}
+
+/// Calibrates the initial temperature for a target acceptance probability.
+///
+/// The calibration performs a random walk of `samples` moves from the initial
+/// state, applying every proposed move, and records the energy difference of
+/// each uphill move. It then computes the temperature at which uphill moves
+/// are accepted with probability `target_acceptance` under the Metropolis
+/// criterion.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::calibration::TemperatureCalibration;
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Point(f64);
+///
+/// impl State for Point {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         Point(self.0 + rng.gen_range(-1.0..1.0))
+///     }
+/// }
+///
+/// struct Square;
+///
+/// impl Energy for Square {
+///     type State = Point;
+///
+///     fn cost(&self, state: &Point) -> f64 {
+///         state.0 * state.0
+///     }
+/// }
+///
+/// let initial_state = Point(5.0);
+/// let mut rng = seeded_rng(42);
+///
+/// // Accept 80% of uphill moves at the start of the run
+/// let initial_temp = TemperatureCalibration::new(0.8)
+///     .calibrate(&Square, &initial_state, &mut rng)
+///     .expect("no uphill moves found");
+///
+/// let mut annealer = Annealer::new(
+///     initial_state,
+///     Square,
+///     GeometricSchedule::new(initial_temp, 0.99),
+///     rng,
+///     10000,
+/// );
+/// let (best_state, best_energy) = annealer.run();
+/// ```
+#[derive(Clone, Debug)]
+pub struct TemperatureCalibration {
+    target_acceptance: f64,
+    samples: usize,
+    method: CalibrationMethod,
+}
+
+impl TemperatureCalibration {
+    /// Creates a new calibration for a target acceptance probability.
+    ///
+    /// By default 1000 moves are sampled and the Ben-Ameur method is used.
+    ///
+    /// # Parameters
+    ///
+    /// * `target_acceptance`: The probability of accepting an uphill move at the initial temperature (between 0 and 1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target_acceptance` is not in (0, 1).
+    pub fn new(target_acceptance: f64) -> Self {
+        assert!(
+            target_acceptance > 0.0 && target_acceptance < 1.0,
+            "Target acceptance must be between 0 and 1 (exclusive)"
+        );
+        Self {
+            target_acceptance,
+            samples: 1000,
+            method: CalibrationMethod::BenAmeur,
+        }
+    }
+
+    /// Sets the number of moves sampled by the random walk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is zero.
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        assert!(samples > 0, "Sample count must be positive");
+        self.samples = samples;
+        self
+    }
+
+    /// Sets the method used to compute the temperature.
+    pub fn with_method(mut self, method: CalibrationMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Samples a random walk and computes the initial temperature.
+    ///
+    /// # Parameters
+    ///
+    /// * `energy`: The energy function of the problem
+    /// * `initial_state`: The state the walk starts from (left unchanged)
+    /// * `rng`: A random number generator
+    ///
+    /// # Returns
+    ///
+    /// The calibrated temperature, or `None` if the walk found no uphill move.
+    pub fn calibrate<E: Energy>(
+        &self,
+        energy: &E,
+        initial_state: &E::State,
+        rng: &mut impl Rng,
+    ) -> Option<f64> {
+        let deltas = self.sample_uphill(energy, initial_state, rng);
+        if deltas.is_empty() {
+            return None;
+        }
+
+        let mean_delta = deltas.iter().sum::<f64>() / deltas.len() as f64;
+        let kirkpatrick = -mean_delta / self.target_acceptance.ln();
+
+        Some(match self.method {
+            CalibrationMethod::Kirkpatrick => kirkpatrick,
+            CalibrationMethod::BenAmeur => self.ben_ameur(&deltas, kirkpatrick),
+        })
+    }
+
+    /// Collects the energy differences of the uphill moves of a random walk.
+    fn sample_uphill<E: Energy>(
+        &self,
+        energy: &E,
+        initial_state: &E::State,
+        rng: &mut impl Rng,
+    ) -> Vec<f64> {
+        let mut state = initial_state.clone();
+        let mut current_energy = energy.cost(&state);
+        let mut deltas = Vec::new();
+
+        for _ in 0..self.samples {
+            let mut mv = state.propose_move(rng);
+            let delta = energy.delta(&mut state, &mut mv, current_energy);
+            if delta > 0.0 {
+                deltas.push(delta);
+            }
+
+            // Every move is applied, so the walk explores around the initial state
+            state.apply_move(&mut mv);
+            current_energy += delta;
+        }
+
+        deltas
+    }
+
+    /// Iterates T <- T * ln(chi(T)) / ln(chi0) until the estimated acceptance
+    /// probability chi(T) matches the target.
+    fn ben_ameur(&self, deltas: &[f64], initial_guess: f64) -> f64 {
+        const TOLERANCE: f64 = 1e-6;
+        const MAX_ROUNDS: usize = 1000;
+
+        let target_ln = self.target_acceptance.ln();
+        let mut temperature = initial_guess;
+
+        for _ in 0..MAX_ROUNDS {
+            let estimate_ln = Self::ln_acceptance(deltas, temperature);
+            if (estimate_ln.exp() - self.target_acceptance).abs() < TOLERANCE {
+                break;
+            }
+            temperature *= estimate_ln / target_ln;
+        }
+
+        temperature
+    }
+
+    /// Computes ln chi(T) = ln(mean exp(-delta/T)), using log-sum-exp so
+    /// that small temperatures do not underflow every term to zero.
+    fn ln_acceptance(deltas: &[f64], temperature: f64) -> f64 {
+        let max = deltas
+            .iter()
+            .map(|delta| -delta / temperature)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let sum: f64 = deltas
+            .iter()
+            .map(|delta| (-delta / temperature - max).exp())
+            .sum();
+        max + (sum / deltas.len() as f64).ln()
+    }
+}
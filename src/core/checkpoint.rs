@@ -0,0 +1,260 @@
+//! Checkpointing support for long annealing runs.
+//!
+//! A checkpoint captures everything needed to continue a run exactly where it
+//! left off: the current and best states, the temperature, the iteration
+//! counter, the statistics, the schedule's internal state, the position of
+//! the random number generator and the tabu memory. States opt in by
+//! implementing `Persist`.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Magic bytes identifying a frostfire checkpoint.
+pub(crate) const MAGIC: &[u8; 4] = b"FFCK";
+
+/// The version of the checkpoint format.
+pub(crate) const VERSION: u32 = 3;
+
+/// Binary serialization for values stored in checkpoints.
+///
+/// Values are written in a compact little-endian format. Implementations are
+/// provided for the primitive numeric types, `bool`, `String`, `Vec<T>`, the
+/// unit type and pairs and triples, so most states can be persisted by writing
+/// their fields in order, and for
+/// `ChaCha12Rng`, the generator used by checkpointed annealers.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::checkpoint::Persist;
+/// use std::io::{self, Read, Write};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Tour {
+///     cities: Vec<usize>,
+///     length: f64,
+/// }
+///
+/// impl Persist for Tour {
+///     fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+///         self.cities.write_to(writer)?;
+///         self.length.write_to(writer)
+///     }
+///
+///     fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+///         Ok(Self {
+///             cities: Vec::read_from(reader)?,
+///             length: f64::read_from(reader)?,
+///         })
+///     }
+/// }
+///
+/// let tour = Tour { cities: vec![2, 0, 1], length: 4.5 };
+/// let mut bytes = Vec::new();
+/// tour.write_to(&mut bytes).unwrap();
+///
+/// let restored = Tour::read_from(&mut bytes.as_slice()).unwrap();
+/// assert_eq!(restored, tour);
+/// ```
+pub trait Persist: Sized {
+    /// Writes the value to a writer.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: The destination of the serialized bytes
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Reads a value previously written with `write_to`.
+    ///
+    /// # Parameters
+    ///
+    /// * `reader`: The source of the serialized bytes
+    ///
+    /// # Returns
+    ///
+    /// The deserialized value, or an error if the data is truncated or invalid.
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self>;
+}
+
+macro_rules! persist_numeric {
+    ($($ty:ty),*) => {
+        $(
+            impl Persist for $ty {
+                fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+                    writer.write_all(&self.to_le_bytes())
+                }
+
+                fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$ty>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$ty>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+persist_numeric!(u8, u16, u32, u64, u128, i8, i16, i32, i64, f32, f64);
+
+/// Sizes are stored as `u64` so checkpoints are portable across platforms.
+impl Persist for usize {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (*self as u64).write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        usize::try_from(u64::read_from(reader)?)
+            .map_err(|_| invalid_data("size does not fit in usize"))
+    }
+}
+
+impl Persist for bool {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (*self as u8).write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        match u8::read_from(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("invalid boolean")),
+        }
+    }
+}
+
+impl Persist for String {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        let len = usize::read_from(reader)?;
+        let mut bytes = Vec::new();
+        reader.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        String::from_utf8(bytes).map_err(|_| invalid_data("invalid UTF-8 string"))
+    }
+}
+
+impl<T: Persist> Persist for Vec<T> {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        for item in self {
+            item.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        let len = usize::read_from(reader)?;
+        // Grow as items are read rather than trusting the length up front
+        let mut items = Vec::new();
+        for _ in 0..len {
+            items.push(T::read_from(reader)?);
+        }
+        Ok(items)
+    }
+}
+
+impl Persist for () {
+    fn write_to(&self, _writer: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn read_from(_reader: &mut dyn Read) -> io::Result<Self> {
+        Ok(())
+    }
+}
+
+impl<A: Persist, B: Persist> Persist for (A, B) {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.0.write_to(writer)?;
+        self.1.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        Ok((A::read_from(reader)?, B::read_from(reader)?))
+    }
+}
+
+impl<A: Persist, B: Persist, C: Persist> Persist for (A, B, C) {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.0.write_to(writer)?;
+        self.1.write_to(writer)?;
+        self.2.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        Ok((
+            A::read_from(reader)?,
+            B::read_from(reader)?,
+            C::read_from(reader)?,
+        ))
+    }
+}
+
+/// The generator's seed, stream and position within the stream, so a restored
+/// generator continues with exactly the numbers the original would have drawn.
+impl Persist for ChaCha12Rng {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(&self.get_seed())?;
+        self.get_stream().write_to(writer)?;
+        self.get_word_pos().write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        let mut seed = <ChaCha12Rng as SeedableRng>::Seed::default();
+        reader.read_exact(&mut seed)?;
+        let mut rng = ChaCha12Rng::from_seed(seed);
+        rng.set_stream(u64::read_from(reader)?);
+        rng.set_word_pos(u128::read_from(reader)?);
+        Ok(rng)
+    }
+}
+
+/// Creates an error for malformed checkpoint data.
+pub(crate) fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Writes the checkpoint header.
+pub(crate) fn write_header(writer: &mut dyn Write) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    VERSION.write_to(writer)
+}
+
+/// Reads and validates the checkpoint header.
+pub(crate) fn read_header(reader: &mut dyn Read) -> io::Result<()> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("not a frostfire checkpoint"));
+    }
+    if u32::read_from(reader)? != VERSION {
+        return Err(invalid_data("unsupported checkpoint version"));
+    }
+    Ok(())
+}
+
+/// Writes a file atomically by writing a temporary file and renaming it.
+///
+/// A crash while writing leaves the previous checkpoint intact.
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut temp_path = PathBuf::from(path);
+    let mut file_name = temp_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+    temp_path.set_file_name(file_name);
+
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&temp_path, path)
+}
@@ -0,0 +1,431 @@
+//! Combinators for building cooling schedules out of simpler ones.
+//!
+//! The wrappers in this module work with any `Schedule`, including
+//! user-defined ones, and can be nested freely. They are most conveniently
+//! built through the `ScheduleExt` extension trait:
+//!
+//! ```
+//! use frostfire::core::combinator::ScheduleExt;
+//! use frostfire::prelude::*;
+//!
+//! // Geometric cooling for 5000 iterations, then a linear finish,
+//! // holding each temperature for 10 iterations and never dropping below 0.01
+//! let schedule = GeometricSchedule::new(100.0, 0.99)
+//!     .then(LinearSchedule::new(1.0, 0.01, 5000), 5000)
+//!     .plateau(10)
+//!     .clamped(0.01, f64::INFINITY);
+//! ```
+//!
+//! Every wrapper forwards `observe`, `save_state` and `load_state` to the
+//! schedules it wraps, so adaptive schedules keep adapting and checkpoints
+//! capture their state.
+
+use crate::core::schedule::{Feedback, Schedule};
+use std::io::{self, Read, Write};
+
+/// Extension methods for composing schedules, implemented for every `Schedule`.
+pub trait ScheduleExt: Schedule + Sized {
+    /// Holds each temperature for `length` iterations before moving on.
+    ///
+    /// See `Plateau` for details.
+    fn plateau(self, length: usize) -> Plateau<Self> {
+        Plateau::new(self, length)
+    }
+
+    /// Switches to another schedule once `at` iterations have been performed.
+    ///
+    /// See `Piecewise` for details.
+    fn then<B: Schedule>(self, next: B, at: usize) -> Piecewise<Self, B> {
+        Piecewise::new(self, next, at)
+    }
+
+    /// Keeps the temperature within `[min, max]`.
+    ///
+    /// See `Clamp` for details.
+    fn clamped(self, min: f64, max: f64) -> Clamp<Self> {
+        Clamp::new(self, min, max)
+    }
+
+    /// Multiplies every temperature by a constant factor.
+    ///
+    /// See `Scale` for details.
+    fn scaled(self, factor: f64) -> Scale<Self> {
+        Scale::new(self, factor)
+    }
+
+    /// Restarts the schedule from its initial temperature every `period` iterations.
+    ///
+    /// See `Cycle` for details.
+    fn cycled(self, period: usize) -> Cycle<Self> {
+        Cycle::new(self, period)
+    }
+}
+
+impl<S: Schedule> ScheduleExt for S {}
+
+/// Holds each temperature of a schedule for a fixed number of iterations.
+///
+/// This is the classic Markov chain length of simulated annealing: the search
+/// runs `length` iterations at each temperature level, and the wrapped
+/// schedule advances by one step at the end of each level. The wrapped
+/// schedule sees the level number as its iteration, so a budget schedule over
+/// n iterations spans n levels.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::combinator::ScheduleExt;
+/// use frostfire::prelude::*;
+///
+/// let schedule = GeometricSchedule::new(10.0, 0.5).plateau(3);
+///
+/// // Iterations 0-2 run at 10.0, iterations 3-5 at 5.0
+/// assert_eq!(schedule.next_temp(10.0, 0), 10.0);
+/// assert_eq!(schedule.next_temp(10.0, 1), 10.0);
+/// assert_eq!(schedule.next_temp(10.0, 2), 5.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Plateau<S> {
+    inner: S,
+    length: usize,
+}
+
+impl<S: Schedule> Plateau<S> {
+    /// Creates a new plateau wrapper.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner`: The schedule producing the temperature levels
+    /// * `length`: The number of iterations per temperature level (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is zero.
+    pub fn new(inner: S, length: usize) -> Self {
+        assert!(length > 0, "Plateau length must be positive");
+        Self { inner, length }
+    }
+}
+
+impl<S: Schedule> Schedule for Plateau<S> {
+    fn initial_temp(&self) -> f64 {
+        self.inner.initial_temp()
+    }
+
+    fn next_temp(&self, current_temp: f64, iteration: usize) -> f64 {
+        if (iteration + 1).is_multiple_of(self.length) {
+            self.inner
+                .next_temp(current_temp, (iteration + 1) / self.length - 1)
+        } else {
+            current_temp
+        }
+    }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        self.inner.observe(&Feedback {
+            iteration: feedback.iteration / self.length,
+            ..*feedback
+        });
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.inner.save_state(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.inner.load_state(reader)
+    }
+}
+
+/// Runs one schedule, then switches to another at an iteration boundary.
+///
+/// The first `at` iterations follow `first`. Iteration `at` runs at the
+/// initial temperature of `second`, which then continues with its iteration
+/// counter starting from zero, so budget schedules behave as if they were
+/// run on their own. Set the initial temperature of `second` to where
+/// `first` ends for a seamless transition. Chains of more than two phases
+/// are built by nesting.
+///
+/// Feedback is forwarded only to the schedule that produced the temperature
+/// of the iteration.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::combinator::ScheduleExt;
+/// use frostfire::prelude::*;
+///
+/// // Geometric for 100 iterations, then linear from 1.0 down to 0.1
+/// let schedule = GeometricSchedule::new(10.0, 0.977)
+///     .then(LinearSchedule::new(1.0, 0.1, 100), 100);
+///
+/// assert_eq!(schedule.next_temp(1.02, 99), 1.0);
+/// assert!((schedule.next_temp(1.0, 100) - 0.991).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Piecewise<A, B> {
+    first: A,
+    second: B,
+    at: usize,
+}
+
+impl<A: Schedule, B: Schedule> Piecewise<A, B> {
+    /// Creates a new two-phase schedule.
+    ///
+    /// # Parameters
+    ///
+    /// * `first`: The schedule for the first phase
+    /// * `second`: The schedule for the second phase
+    /// * `at`: The number of iterations in the first phase (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is zero.
+    pub fn new(first: A, second: B, at: usize) -> Self {
+        assert!(at > 0, "Switch iteration must be positive");
+        Self { first, second, at }
+    }
+}
+
+impl<A: Schedule, B: Schedule> Schedule for Piecewise<A, B> {
+    fn initial_temp(&self) -> f64 {
+        self.first.initial_temp()
+    }
+
+    fn next_temp(&self, current_temp: f64, iteration: usize) -> f64 {
+        if iteration + 1 < self.at {
+            self.first.next_temp(current_temp, iteration)
+        } else if iteration + 1 == self.at {
+            self.second.initial_temp()
+        } else {
+            self.second.next_temp(current_temp, iteration - self.at)
+        }
+    }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        if feedback.iteration < self.at {
+            self.first.observe(feedback);
+        } else {
+            self.second.observe(&Feedback {
+                iteration: feedback.iteration - self.at,
+                ..*feedback
+            });
+        }
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.first.save_state(writer)?;
+        self.second.save_state(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.first.load_state(reader)?;
+        self.second.load_state(reader)
+    }
+}
+
+/// Keeps the temperatures of a schedule within fixed bounds.
+///
+/// Use `f64::INFINITY` as the maximum to only enforce a floor.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::combinator::ScheduleExt;
+/// use frostfire::prelude::*;
+///
+/// let schedule = GeometricSchedule::new(10.0, 0.5).clamped(4.0, f64::INFINITY);
+///
+/// assert_eq!(schedule.next_temp(10.0, 0), 5.0);
+/// assert_eq!(schedule.next_temp(5.0, 1), 4.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Clamp<S> {
+    inner: S,
+    min: f64,
+    max: f64,
+}
+
+impl<S: Schedule> Clamp<S> {
+    /// Creates a new clamping wrapper.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner`: The schedule to clamp
+    /// * `min`: The lowest temperature (must be positive)
+    /// * `max`: The highest temperature (must be at least `min`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is not positive or `max` is below `min`.
+    pub fn new(inner: S, min: f64, max: f64) -> Self {
+        assert!(min > 0.0, "Minimum temperature must be positive");
+        assert!(
+            max >= min,
+            "Maximum temperature must not be below the minimum"
+        );
+        Self { inner, min, max }
+    }
+}
+
+impl<S: Schedule> Schedule for Clamp<S> {
+    fn initial_temp(&self) -> f64 {
+        self.inner.initial_temp().clamp(self.min, self.max)
+    }
+
+    fn next_temp(&self, current_temp: f64, iteration: usize) -> f64 {
+        self.inner
+            .next_temp(current_temp, iteration)
+            .clamp(self.min, self.max)
+    }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        self.inner.observe(feedback);
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.inner.save_state(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.inner.load_state(reader)
+    }
+}
+
+/// Multiplies the temperatures of a schedule by a constant factor.
+///
+/// The wrapped schedule runs on its own temperature scale: it receives the
+/// current temperature divided by the factor, and its result is multiplied
+/// by the factor. This makes it easy to reuse a tuned schedule for a problem
+/// whose energies have a different scale.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::combinator::ScheduleExt;
+/// use frostfire::prelude::*;
+///
+/// let schedule = LinearSchedule::new(10.0, 1.0, 9).scaled(100.0);
+///
+/// assert_eq!(schedule.initial_temp(), 1000.0);
+/// assert!((schedule.next_temp(1000.0, 0) - 900.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scale<S> {
+    inner: S,
+    factor: f64,
+}
+
+impl<S: Schedule> Scale<S> {
+    /// Creates a new scaling wrapper.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner`: The schedule to scale
+    /// * `factor`: The factor applied to every temperature (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive.
+    pub fn new(inner: S, factor: f64) -> Self {
+        assert!(factor > 0.0, "Scale factor must be positive");
+        Self { inner, factor }
+    }
+}
+
+impl<S: Schedule> Schedule for Scale<S> {
+    fn initial_temp(&self) -> f64 {
+        self.inner.initial_temp() * self.factor
+    }
+
+    fn next_temp(&self, current_temp: f64, iteration: usize) -> f64 {
+        self.inner.next_temp(current_temp / self.factor, iteration) * self.factor
+    }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        self.inner.observe(&Feedback {
+            temperature: feedback.temperature / self.factor,
+            ..*feedback
+        });
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.inner.save_state(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.inner.load_state(reader)
+    }
+}
+
+/// Repeats a schedule in cycles of a fixed number of iterations.
+///
+/// Every `period` iterations the temperature returns to the initial
+/// temperature of the wrapped schedule, which then sees its iteration counter
+/// start over. Any state the wrapped schedule has learned from feedback is
+/// kept across cycles.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::combinator::ScheduleExt;
+/// use frostfire::prelude::*;
+///
+/// let schedule = GeometricSchedule::new(10.0, 0.5).cycled(3);
+///
+/// assert_eq!(schedule.next_temp(10.0, 0), 5.0);
+/// assert_eq!(schedule.next_temp(5.0, 1), 2.5);
+/// // The cycle restarts at iteration 3
+/// assert_eq!(schedule.next_temp(2.5, 2), 10.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cycle<S> {
+    inner: S,
+    period: usize,
+}
+
+impl<S: Schedule> Cycle<S> {
+    /// Creates a new cyclic wrapper.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner`: The schedule to repeat
+    /// * `period`: The number of iterations per cycle (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn new(inner: S, period: usize) -> Self {
+        assert!(period > 0, "Cycle period must be positive");
+        Self { inner, period }
+    }
+}
+
+impl<S: Schedule> Schedule for Cycle<S> {
+    fn initial_temp(&self) -> f64 {
+        self.inner.initial_temp()
+    }
+
+    fn next_temp(&self, current_temp: f64, iteration: usize) -> f64 {
+        if (iteration + 1).is_multiple_of(self.period) {
+            self.inner.initial_temp()
+        } else {
+            self.inner.next_temp(current_temp, iteration % self.period)
+        }
+    }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        self.inner.observe(&Feedback {
+            iteration: feedback.iteration % self.period,
+            ..*feedback
+        });
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.inner.save_state(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.inner.load_state(reader)
+    }
+}
@@ -3,7 +3,7 @@
 //! The `Energy` trait defines the cost function to be minimized
 //! during the simulated annealing process.
 
-use crate::core::state::State;
+use crate::core::state::MoveState;
 
 /// The `Energy` trait defines the cost function to be minimized
 /// during the simulated annealing process.
@@ -39,7 +39,9 @@ use crate::core::state::State;
 /// ```
 pub trait Energy {
     /// The type of state this energy function evaluates.
-    type State: State;
+    ///
+    /// Any `State` qualifies, since every `State` is also a `MoveState`.
+    type State: MoveState;
 
     /// Calculates the cost (energy) of a given state.
     ///
@@ -55,4 +57,93 @@ pub trait Energy {
     /// The cost (energy) of the given state as a floating-point value.
     /// Lower values are considered better in the annealing process.
     fn cost(&self, state: &Self::State) -> f64;
+
+    /// Calculates the energy difference caused by applying `mv` to `state`.
+    ///
+    /// The annealer calls this method instead of `cost` for every proposed move,
+    /// so overriding it lets problems with cheap incremental updates (for example
+    /// a 2-opt or swap move in a tour) avoid a full O(n) re-evaluation.
+    ///
+    /// The default implementation applies the move in place, evaluates `cost`,
+    /// and reverts the move, so implementors that only provide `cost` keep
+    /// working unchanged. For neighbor-based `State`s the move is the neighboring
+    /// state itself.
+    ///
+    /// The state is borrowed mutably only to allow that in-place evaluation;
+    /// implementations must leave `state` and `mv` as they found them.
+    ///
+    /// # Parameters
+    ///
+    /// * `state`: The current state
+    /// * `mv`: The proposed move, not yet applied to `state`
+    /// * `current_energy`: The energy of `state`, as tracked by the annealer
+    ///
+    /// # Returns
+    ///
+    /// The energy difference between the state after the move and `state`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::prelude::*;
+    /// use rand::Rng;
+    ///
+    /// #[derive(Clone)]
+    /// struct Point(Vec<f64>);
+    ///
+    /// impl MoveState for Point {
+    ///     // Index of the coordinate to change and the new value
+    ///     type Move = (usize, f64);
+    ///
+    ///     fn propose_move(&self, rng: &mut impl Rng) -> Self::Move {
+    ///         let idx = rng.gen_range(0..self.0.len());
+    ///         (idx, self.0[idx] + rng.gen_range(-0.1..0.1))
+    ///     }
+    ///
+    ///     fn apply_move(&mut self, mv: &mut Self::Move) {
+    ///         // Keep the old value in the move so it can be undone
+    ///         std::mem::swap(&mut self.0[mv.0], &mut mv.1);
+    ///     }
+    ///
+    ///     fn undo_move(&mut self, mv: &mut Self::Move) {
+    ///         std::mem::swap(&mut self.0[mv.0], &mut mv.1);
+    ///     }
+    /// }
+    ///
+    /// struct SumOfSquares;
+    ///
+    /// impl Energy for SumOfSquares {
+    ///     type State = Point;
+    ///
+    ///     fn cost(&self, state: &Self::State) -> f64 {
+    ///         state.0.iter().map(|x| x * x).sum()
+    ///     }
+    ///
+    ///     fn delta(&self, state: &mut Point, mv: &mut (usize, f64), _current_energy: f64) -> f64 {
+    ///         // Only one coordinate changes, so the delta is O(1)
+    ///         let (idx, new) = *mv;
+    ///         new * new - state.0[idx] * state.0[idx]
+    ///     }
+    /// }
+    ///
+    /// let mut state = Point(vec![1.0, 2.0]);
+    /// let mut mv = state.propose_move(&mut seeded_rng(7));
+    /// let current_energy = SumOfSquares.cost(&state);
+    /// let delta = SumOfSquares.delta(&mut state, &mut mv, current_energy);
+    ///
+    /// state.apply_move(&mut mv);
+    /// let expected = SumOfSquares.cost(&state) - current_energy;
+    /// assert!((delta - expected).abs() < 1e-12);
+    /// ```
+    fn delta(
+        &self,
+        state: &mut Self::State,
+        mv: &mut <Self::State as MoveState>::Move,
+        current_energy: f64,
+    ) -> f64 {
+        state.apply_move(mv);
+        let new_energy = self.cost(state);
+        state.undo_move(mv);
+        new_energy - current_energy
+    }
 }
@@ -0,0 +1,337 @@
+//! Equilibrium-driven temperature levels for simulated annealing.
+//!
+//! By default the `Annealer` asks the schedule for a new temperature after
+//! every iteration. With an `Equilibrium` criterion the run is organized in
+//! temperature levels (epochs) instead: each level runs at a fixed temperature
+//! until the search reaches quasi-equilibrium or a length cap, and only then
+//! does the schedule advance. Statistics for every level are reported in
+//! `AnnealingResult::levels`.
+
+use crate::core::checkpoint::Persist;
+use std::io::{self, Read, Write};
+
+/// When a temperature level is considered to be in equilibrium.
+#[derive(Clone, Debug)]
+enum Criterion {
+    /// After a number of accepted moves
+    AcceptedMoves { target: usize },
+    /// When the energy statistics of consecutive windows agree
+    EnergyStability { window: usize, tolerance: f64 },
+}
+
+/// A criterion ending each temperature level of the `Annealer`.
+///
+/// Every level ends when its criterion is met or when it reaches
+/// `max_length` iterations, whichever comes first. The schedule then computes
+/// the temperature of the next level, seeing the level number (counted from
+/// the start of the run or the last reheat) as its iteration. A reheat also
+/// ends the current level.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::equilibrium::Equilibrium;
+/// use frostfire::prelude::*;
+///
+/// # #[derive(Clone)]
+/// # struct MyState(f64);
+/// # impl State for MyState {
+/// #     fn neighbor(&self, rng: &mut impl rand::Rng) -> Self { MyState(self.0 + rng.gen_range(-1.0..1.0)) }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, state: &Self::State) -> f64 { state.0 * state.0 }
+/// # }
+/// // Stay at each temperature for 50 accepted moves, or at most 500 iterations
+/// let mut annealer = Annealer::new(
+///     MyState(10.0),
+///     MyEnergy,
+///     GeometricSchedule::new(10.0, 0.9),
+///     seeded_rng(42),
+///     10000,
+/// )
+/// .with_equilibrium(Equilibrium::accepted_moves(50, 500));
+///
+/// let result = annealer.run_with_stats();
+/// for level in &result.levels {
+///     println!(
+///         "T = {:.4}: {} iterations, acceptance {:.2}, mean energy {:.4}",
+///         level.temperature,
+///         level.iterations,
+///         level.acceptance_ratio(),
+///         level.mean_energy
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Equilibrium {
+    criterion: Criterion,
+    max_length: usize,
+}
+
+impl Equilibrium {
+    /// Ends each level after a number of accepted moves.
+    ///
+    /// Counting accepted rather than attempted moves makes levels longer at
+    /// low temperatures, where most moves are rejected.
+    ///
+    /// # Parameters
+    ///
+    /// * `target`: The number of accepted moves per level (must be positive)
+    /// * `max_length`: The maximum number of iterations per level (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` or `max_length` is zero.
+    pub fn accepted_moves(target: usize, max_length: usize) -> Self {
+        assert!(target > 0, "Accepted move target must be positive");
+        assert!(max_length > 0, "Maximum level length must be positive");
+        Self {
+            criterion: Criterion::AcceptedMoves { target },
+            max_length,
+        }
+    }
+
+    /// Ends each level once the energy distribution stabilizes, in the spirit
+    /// of Aarts and van Laarhoven.
+    ///
+    /// The level is split into windows of `window` iterations. After each
+    /// window, the mean and variance of the current energy are compared with
+    /// those of the previous window; the level is in equilibrium when
+    ///
+    /// |mean - previous mean| <= tolerance * previous standard deviation, and
+    /// |variance - previous variance| <= tolerance * previous variance.
+    ///
+    /// # Parameters
+    ///
+    /// * `window`: The number of iterations per window (must be at least 2)
+    /// * `tolerance`: The relative tolerance of the comparison (must be positive)
+    /// * `max_length`: The maximum number of iterations per level (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is less than 2, `tolerance` is not positive or `max_length` is zero.
+    pub fn energy_stability(window: usize, tolerance: f64, max_length: usize) -> Self {
+        assert!(window >= 2, "Window size must be at least 2");
+        assert!(tolerance > 0.0, "Tolerance must be positive");
+        assert!(max_length > 0, "Maximum level length must be positive");
+        Self {
+            criterion: Criterion::EnergyStability { window, tolerance },
+            max_length,
+        }
+    }
+
+    /// Returns the maximum number of iterations per level.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+/// Statistics of one temperature level of a run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LevelStats {
+    /// The temperature of the level
+    pub temperature: f64,
+    /// The number of iterations performed at this temperature
+    pub iterations: usize,
+    /// The number of moves accepted at this temperature
+    pub accepted_moves: usize,
+    /// The mean of the current energy over the level
+    pub mean_energy: f64,
+    /// The variance of the current energy over the level
+    pub energy_variance: f64,
+    /// The best energy found by the end of the level
+    pub best_energy: f64,
+    /// Whether the level ended by reaching equilibrium, rather than at its
+    /// length cap, on a reheat or at the end of the run
+    pub equilibrated: bool,
+}
+
+impl LevelStats {
+    /// Returns the fraction of moves accepted at this temperature.
+    pub fn acceptance_ratio(&self) -> f64 {
+        self.accepted_moves as f64 / self.iterations as f64
+    }
+}
+
+impl Persist for LevelStats {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.temperature.write_to(writer)?;
+        self.iterations.write_to(writer)?;
+        self.accepted_moves.write_to(writer)?;
+        self.mean_energy.write_to(writer)?;
+        self.energy_variance.write_to(writer)?;
+        self.best_energy.write_to(writer)?;
+        self.equilibrated.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        Ok(Self {
+            temperature: f64::read_from(reader)?,
+            iterations: usize::read_from(reader)?,
+            accepted_moves: usize::read_from(reader)?,
+            mean_energy: f64::read_from(reader)?,
+            energy_variance: f64::read_from(reader)?,
+            best_energy: f64::read_from(reader)?,
+            equilibrated: bool::read_from(reader)?,
+        })
+    }
+}
+
+/// Running mean and variance, updated with Welford's algorithm.
+#[derive(Clone, Copy, Debug, Default)]
+struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// The population variance of the values pushed so far.
+    fn variance(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.m2 / self.count as f64
+        }
+    }
+}
+
+impl Persist for RunningStats {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.count.write_to(writer)?;
+        self.mean.write_to(writer)?;
+        self.m2.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        Ok(Self {
+            count: usize::read_from(reader)?,
+            mean: f64::read_from(reader)?,
+            m2: f64::read_from(reader)?,
+        })
+    }
+}
+
+/// The temperature level in progress.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Epoch {
+    accepted: usize,
+    energy: RunningStats,
+    window: RunningStats,
+    /// The mean and variance of the previous complete window
+    previous_window: Option<(f64, f64)>,
+}
+
+impl Epoch {
+    /// Returns whether no iterations have been performed in this level.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.energy.count == 0
+    }
+
+    /// Records an iteration of the level.
+    ///
+    /// # Returns
+    ///
+    /// `Some(equilibrated)` if the level is complete, where `equilibrated`
+    /// tells whether the criterion was met rather than the length cap.
+    pub(crate) fn record(
+        &mut self,
+        equilibrium: &Equilibrium,
+        accepted: bool,
+        energy: f64,
+    ) -> Option<bool> {
+        if accepted {
+            self.accepted += 1;
+        }
+        self.energy.push(energy);
+
+        let equilibrated = match equilibrium.criterion {
+            Criterion::AcceptedMoves { target } => self.accepted >= target,
+            Criterion::EnergyStability { window, tolerance } => {
+                self.window.push(energy);
+                if self.window.count < window {
+                    false
+                } else {
+                    let mean = self.window.mean;
+                    let variance = self.window.variance();
+                    self.window = RunningStats::default();
+                    self.previous_window.replace((mean, variance)).is_some_and(
+                        |(previous_mean, previous_variance)| {
+                            (mean - previous_mean).abs() <= tolerance * previous_variance.sqrt()
+                                && (variance - previous_variance).abs()
+                                    <= tolerance * previous_variance
+                        },
+                    )
+                }
+            }
+        };
+
+        if equilibrated {
+            Some(true)
+        } else if self.energy.count >= equilibrium.max_length {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Summarizes the level.
+    pub(crate) fn stats(
+        &self,
+        temperature: f64,
+        best_energy: f64,
+        equilibrated: bool,
+    ) -> LevelStats {
+        LevelStats {
+            temperature,
+            iterations: self.energy.count,
+            accepted_moves: self.accepted,
+            mean_energy: self.energy.mean,
+            energy_variance: self.energy.variance(),
+            best_energy,
+            equilibrated,
+        }
+    }
+}
+
+impl Persist for Epoch {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.accepted.write_to(writer)?;
+        self.energy.write_to(writer)?;
+        self.window.write_to(writer)?;
+        match self.previous_window {
+            Some((mean, variance)) => {
+                true.write_to(writer)?;
+                mean.write_to(writer)?;
+                variance.write_to(writer)
+            }
+            None => false.write_to(writer),
+        }
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        let accepted = usize::read_from(reader)?;
+        let energy = RunningStats::read_from(reader)?;
+        let window = RunningStats::read_from(reader)?;
+        let previous_window = if bool::read_from(reader)? {
+            Some((f64::read_from(reader)?, f64::read_from(reader)?))
+        } else {
+            None
+        };
+        Ok(Self {
+            accepted,
+            energy,
+            window,
+            previous_window,
+        })
+    }
+}
@@ -4,13 +4,43 @@
 //! that form the backbone of the frostfire library:
 //!
 //! - `annealer`: The main optimization engine
+//! - `tempering`: A parallel tempering (replica exchange) engine
+//! - `population`: A population annealing engine with Boltzmann resampling
+//! - `multistart`: A deterministic parallel multi-start runner
+//! - `multiobjective`: Multi-objective annealing with a Pareto archive
+//! - `sampler`: Fixed-temperature Metropolis sampling with autocorrelation diagnostics
+//! - `wang_landau`: Wang–Landau estimation of the density of states
+//! - `ais`: Annealed importance sampling of partition function ratios
 //! - `state`: The representation of candidate solutions
 //! - `energy`: The cost function to be minimized
+//! - `observer`: Callback hooks for watching the annealing loop
 //! - `transition`: Acceptance criteria for proposed state transitions
 //! - `schedule`: Cooling schedules that control the annealing process
+//! - `combinator`: Wrappers for composing cooling schedules
+//! - `calibration`: Automatic calibration of the initial temperature
+//! - `equilibrium`: Temperature levels that run until quasi-equilibrium
+//! - `reheat`: Reheating and restart strategies for escaping frozen basins
+//! - `tabu`: Tabu memory rejecting proposals that repeat recent moves
+//! - `termination`: Composable stopping conditions for the annealing process
+//! - `checkpoint`: Serialization of runs in progress for checkpoint and resume
 
+pub mod ais;
 pub mod annealer;
+pub mod calibration;
+pub mod checkpoint;
+pub mod combinator;
 pub mod energy;
+pub mod equilibrium;
+pub mod multiobjective;
+pub mod multistart;
+pub mod observer;
+pub mod population;
+pub mod reheat;
+pub mod sampler;
 pub mod schedule;
 pub mod state;
+pub mod tabu;
+pub mod tempering;
+pub mod termination;
 pub mod transition;
+pub mod wang_landau;
@@ -0,0 +1,560 @@
+//! Multi-objective simulated annealing.
+//!
+//! Many problems trade off several costs, such as cost against lateness, with
+//! no single best solution. This module implements an AMOSA-style annealer
+//! (Bandyopadhyay et al., 2008) that scores moves by how much they are
+//! dominated and keeps a bounded archive of non-dominated solutions,
+//! approximating the Pareto front.
+
+use crate::core::schedule::Schedule;
+use crate::core::state::MoveState;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt;
+
+/// A cost function with several objectives, all to be minimized.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+///
+/// #[derive(Clone)]
+/// struct Plan {
+///     cost: f64,
+///     lateness: f64,
+/// }
+///
+/// # impl State for Plan {
+/// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+/// # }
+/// struct PlanObjectives;
+///
+/// impl MultiEnergy for PlanObjectives {
+///     type State = Plan;
+///
+///     fn objectives(&self) -> usize {
+///         2
+///     }
+///
+///     fn costs(&self, state: &Plan) -> Vec<f64> {
+///         vec![state.cost, state.lateness]
+///     }
+/// }
+/// ```
+pub trait MultiEnergy {
+    /// The type of state this energy function evaluates.
+    type State: MoveState;
+
+    /// Returns the number of objectives.
+    fn objectives(&self) -> usize;
+
+    /// Evaluates every objective of a state.
+    ///
+    /// # Parameters
+    ///
+    /// * `state`: The state to evaluate
+    ///
+    /// # Returns
+    ///
+    /// The value of each objective, `objectives()` values in a fixed order.
+    fn costs(&self, state: &Self::State) -> Vec<f64>;
+}
+
+/// Returns whether the objectives `a` Pareto-dominate the objectives `b`.
+///
+/// `a` dominates `b` when it is no worse in every objective and strictly
+/// better in at least one.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::multiobjective::dominates;
+///
+/// assert!(dominates(&[1.0, 2.0], &[1.0, 3.0]));
+/// assert!(!dominates(&[1.0, 3.0], &[2.0, 2.0]));
+/// assert!(!dominates(&[1.0, 2.0], &[1.0, 2.0]));
+/// ```
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x <= y) && a.iter().zip(b).any(|(x, y)| x < y)
+}
+
+/// A solution in a Pareto archive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParetoPoint<S> {
+    /// The solution
+    pub state: S,
+    /// The value of each objective for the solution
+    pub objectives: Vec<f64>,
+}
+
+/// A bounded archive of mutually non-dominated solutions.
+///
+/// Inserting a solution that is dominated by (or equal to) an archived one
+/// has no effect; inserting a non-dominated solution removes the archived
+/// solutions it dominates. When the archive grows beyond its capacity, the
+/// solutions in the most crowded regions of objective space are removed,
+/// using the crowding distance of NSGA-II, so the archive stays spread along
+/// the front. Solutions at the extreme of an objective are only removed when
+/// every archived solution is extreme in some objective; then those extreme in
+/// the fewest objectives go first, the most crowded over their other objectives
+/// among them.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::multiobjective::ParetoArchive;
+///
+/// let mut archive = ParetoArchive::new(10);
+/// assert!(archive.insert("a", vec![1.0, 3.0]));
+/// assert!(archive.insert("b", vec![3.0, 1.0]));
+/// assert!(!archive.insert("c", vec![3.0, 3.0]));
+///
+/// // Dominates "a"
+/// assert!(archive.insert("d", vec![1.0, 2.0]));
+/// assert_eq!(archive.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParetoArchive<S> {
+    points: Vec<ParetoPoint<S>>,
+    capacity: usize,
+}
+
+impl<S> ParetoArchive<S> {
+    /// Creates an empty archive.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity`: The maximum number of solutions kept (must be at least 2)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is less than 2.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity >= 2, "Archive capacity must be at least 2");
+        Self {
+            points: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of solutions kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of archived solutions.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns whether the archive is empty.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the archived solutions.
+    pub fn points(&self) -> &[ParetoPoint<S>] {
+        &self.points
+    }
+
+    /// Offers a solution to the archive.
+    ///
+    /// # Parameters
+    ///
+    /// * `state`: The solution
+    /// * `objectives`: The value of each objective for the solution
+    ///
+    /// # Returns
+    ///
+    /// Whether the solution was added. A solution that is added may still be
+    /// pruned right away if it lies in the most crowded region.
+    pub fn insert(&mut self, state: S, objectives: Vec<f64>) -> bool {
+        if self.points.iter().any(|point| {
+            point.objectives == objectives || dominates(&point.objectives, &objectives)
+        }) {
+            return false;
+        }
+
+        self.points
+            .retain(|point| !dominates(&objectives, &point.objectives));
+        self.points.push(ParetoPoint { state, objectives });
+
+        while self.points.len() > self.capacity {
+            let crowding = self.crowding();
+            let most_crowded = crowding
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)))
+                .map(|(i, _)| i)
+                .expect("archive is not empty");
+            self.points.swap_remove(most_crowded);
+        }
+        true
+    }
+
+    /// Returns the archived solutions sorted by their objectives, in
+    /// lexicographic order.
+    pub fn into_front(self) -> Vec<ParetoPoint<S>> {
+        let mut front = self.points;
+        front.sort_by(|a, b| {
+            a.objectives
+                .iter()
+                .zip(&b.objectives)
+                .map(|(x, y)| x.total_cmp(y))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        front
+    }
+
+    /// Computes, for each archived solution, the number of objectives in which
+    /// it is extreme and its crowding distance over the other objectives.
+    fn crowding(&self) -> Vec<(usize, f64)> {
+        let count = self.points.len();
+        let mut crowding = vec![(0, 0.0); count];
+        let objectives = self.points.first().map_or(0, |p| p.objectives.len());
+
+        let mut order: Vec<usize> = (0..count).collect();
+        for m in 0..objectives {
+            order.sort_by(|&a, &b| {
+                self.points[a].objectives[m].total_cmp(&self.points[b].objectives[m])
+            });
+            let low = self.points[order[0]].objectives[m];
+            let high = self.points[order[count - 1]].objectives[m];
+
+            // An objective on which all solutions agree does not separate them
+            if high > low {
+                crowding[order[0]].0 += 1;
+                crowding[order[count - 1]].0 += 1;
+                for k in 1..count - 1 {
+                    let gap = self.points[order[k + 1]].objectives[m]
+                        - self.points[order[k - 1]].objectives[m];
+                    crowding[order[k]].1 += gap / (high - low);
+                }
+            }
+        }
+        crowding
+    }
+}
+
+/// Results from a multi-objective annealing run.
+#[derive(Clone)]
+pub struct MultiObjectiveResult<S: MoveState> {
+    /// The approximated Pareto front, sorted by objectives in lexicographic order
+    pub front: Vec<ParetoPoint<S>>,
+    /// The final current state
+    pub final_state: S,
+    /// The objectives of the final current state
+    pub final_objectives: Vec<f64>,
+    /// The temperature at the end of the run
+    pub final_temperature: f64,
+    /// The number of iterations performed
+    pub iterations: usize,
+    /// The number of accepted moves
+    pub accepted_moves: usize,
+    /// The number of rejected moves
+    pub rejected_moves: usize,
+}
+
+impl<S: MoveState> fmt::Debug for MultiObjectiveResult<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiObjectiveResult")
+            .field("front", &self.front.len())
+            .field("final_objectives", &self.final_objectives)
+            .field("final_temperature", &self.final_temperature)
+            .field("iterations", &self.iterations)
+            .field("accepted_moves", &self.accepted_moves)
+            .field("rejected_moves", &self.rejected_moves)
+            .finish()
+    }
+}
+
+/// Multi-objective simulated annealer in the style of AMOSA.
+///
+/// Each iteration proposes a move from the current state and compares the
+/// objectives of the new state with those of the current state and the
+/// archive. The amount by which `a` dominates `b` is
+///
+/// dom(a, b) = product over objectives where they differ of |a_i - b_i| / R_i
+///
+/// where R_i is the range of objective i over the archive and both states. A
+/// move is then handled as follows:
+///
+/// - If the current state dominates the new one, the move is accepted with
+///   probability 1 / (1 + exp(d / T)), where d is the mean amount by which the
+///   current state and the k archived solutions dominating the new state
+///   dominate it.
+/// - If neither dominates the other, the move is accepted outright unless
+///   archived solutions dominate the new state; then it is accepted with the
+///   same probability, d averaging over those solutions.
+/// - If the new state dominates the current one, the move is accepted. When
+///   archived solutions still dominate the new state, the annealer instead
+///   jumps to the one dominating it least, with probability
+///   1 / (1 + exp(-d_min)).
+///
+/// Accepted states are offered to the `ParetoArchive`. The temperature
+/// follows the schedule, one step per iteration; since the moves have no
+/// single energy difference, the schedule receives no feedback.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Point(f64);
+///
+/// impl State for Point {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         Point((self.0 + rng.gen_range(-0.2..0.2)).clamp(-1.0, 3.0))
+///     }
+/// }
+///
+/// // Schaffer's problem: the Pareto set is 0 <= x <= 2
+/// struct Schaffer;
+///
+/// impl MultiEnergy for Schaffer {
+///     type State = Point;
+///
+///     fn objectives(&self) -> usize {
+///         2
+///     }
+///
+///     fn costs(&self, state: &Point) -> Vec<f64> {
+///         vec![state.0 * state.0, (state.0 - 2.0).powi(2)]
+///     }
+/// }
+///
+/// let mut annealer = MultiObjectiveAnnealer::new(
+///     Point(-1.0),
+///     Schaffer,
+///     GeometricSchedule::new(1.0, 0.999),
+///     seeded_rng(42),
+///     5000,
+/// )
+/// .with_archive_capacity(20);
+///
+/// let result = annealer.run();
+/// assert!(result.front.len() > 1 && result.front.len() <= 20);
+/// for point in &result.front {
+///     println!("x = {:.3}: {:?}", point.state.0, point.objectives);
+/// }
+/// ```
+pub struct MultiObjectiveAnnealer<S, E, Sch>
+where
+    S: MoveState,
+    E: MultiEnergy<State = S>,
+    Sch: Schedule,
+{
+    state: S,
+    energy: E,
+    schedule: Sch,
+    rng: StdRng,
+    max_iters: usize,
+    archive_capacity: usize,
+}
+
+impl<S, E, Sch> MultiObjectiveAnnealer<S, E, Sch>
+where
+    S: MoveState,
+    E: MultiEnergy<State = S>,
+    Sch: Schedule,
+{
+    /// Creates a new multi-objective annealer.
+    ///
+    /// By default the archive keeps up to 100 solutions.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_state`: The starting state
+    /// * `energy`: The objectives to be minimized
+    /// * `schedule`: The cooling schedule
+    /// * `rng`: The random number generator
+    /// * `max_iters`: The number of iterations to perform
+    pub fn new(initial_state: S, energy: E, schedule: Sch, rng: StdRng, max_iters: usize) -> Self {
+        Self {
+            state: initial_state,
+            energy,
+            schedule,
+            rng,
+            max_iters,
+            archive_capacity: 100,
+        }
+    }
+
+    /// Sets the maximum number of solutions kept in the archive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is less than 2.
+    pub fn with_archive_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity >= 2, "Archive capacity must be at least 2");
+        self.archive_capacity = capacity;
+        self
+    }
+
+    /// Runs the annealer and returns the approximated Pareto front.
+    ///
+    /// # Returns
+    ///
+    /// A `MultiObjectiveResult` with the archived front and statistics of the run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the energy returns a number of costs different from `objectives()`.
+    pub fn run(&mut self) -> MultiObjectiveResult<S> {
+        let mut archive = ParetoArchive::new(self.archive_capacity);
+        let mut current = self.evaluate(&self.state);
+        archive.insert(self.state.clone(), current.clone());
+
+        let mut temperature = self.schedule.initial_temp();
+        let mut accepted_moves = 0;
+        let mut rejected_moves = 0;
+
+        for iteration in 0..self.max_iters {
+            if self.iterate(&mut archive, &mut current, temperature) {
+                accepted_moves += 1;
+            } else {
+                rejected_moves += 1;
+            }
+            temperature = self.schedule.next_temp(temperature, iteration);
+        }
+
+        MultiObjectiveResult {
+            front: archive.into_front(),
+            final_state: self.state.clone(),
+            final_objectives: current,
+            final_temperature: temperature,
+            iterations: self.max_iters,
+            accepted_moves,
+            rejected_moves,
+        }
+    }
+
+    /// Performs one iteration, returning whether the move was accepted.
+    fn iterate(
+        &mut self,
+        archive: &mut ParetoArchive<S>,
+        current: &mut Vec<f64>,
+        temperature: f64,
+    ) -> bool {
+        let mut mv = self.state.propose_move(&mut self.rng);
+        self.state.apply_move(&mut mv);
+        let candidate = self.evaluate(&self.state);
+
+        let ranges = objective_ranges(archive, current, &candidate);
+        // The archived solutions dominating the candidate, with the amount of domination
+        let dominating: Vec<(usize, f64)> = archive
+            .points()
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| dominates(&point.objectives, &candidate))
+            .map(|(i, point)| (i, domination_amount(&point.objectives, &candidate, &ranges)))
+            .collect();
+        let total: f64 = dominating.iter().map(|&(_, amount)| amount).sum();
+
+        let outcome = if dominates(current, &candidate) {
+            let total = total + domination_amount(current, &candidate, &ranges);
+            let mean = total / (dominating.len() + 1) as f64;
+            self.metropolis(mean, temperature)
+        } else if !dominates(&candidate, current) {
+            if dominating.is_empty() {
+                Outcome::Accept
+            } else {
+                self.metropolis(total / dominating.len() as f64, temperature)
+            }
+        } else {
+            // Jump to the archived solution dominating the candidate least
+            match dominating.iter().min_by(|a, b| a.1.total_cmp(&b.1)) {
+                Some(&(index, amount)) if self.rng.gen::<f64>() < 1.0 / (1.0 + (-amount).exp()) => {
+                    Outcome::Jump(index)
+                }
+                _ => Outcome::Accept,
+            }
+        };
+
+        match outcome {
+            Outcome::Accept => {
+                archive.insert(self.state.clone(), candidate.clone());
+                *current = candidate;
+            }
+            Outcome::Reject => self.state.undo_move(&mut mv),
+            Outcome::Jump(index) => {
+                let point = &archive.points()[index];
+                self.state.clone_from(&point.state);
+                current.clone_from(&point.objectives);
+            }
+        }
+        !matches!(outcome, Outcome::Reject)
+    }
+
+    /// Accepts a dominated candidate with the probability given by its mean
+    /// domination amount.
+    fn metropolis(&mut self, mean: f64, temperature: f64) -> Outcome {
+        if self.rng.gen::<f64>() < acceptance_probability(mean, temperature) {
+            Outcome::Accept
+        } else {
+            Outcome::Reject
+        }
+    }
+
+    /// Evaluates the objectives of a state, checking their number.
+    fn evaluate(&self, state: &S) -> Vec<f64> {
+        let costs = self.energy.costs(state);
+        assert_eq!(
+            costs.len(),
+            self.energy.objectives(),
+            "Energy returned the wrong number of objectives"
+        );
+        costs
+    }
+}
+
+/// What an iteration does with a proposed move.
+enum Outcome {
+    /// Keep the candidate and offer it to the archive
+    Accept,
+    /// Undo the move
+    Reject,
+    /// Replace the current state with the archived solution at an index
+    Jump(usize),
+}
+
+/// The range of each objective over the archive and two more points, with
+/// empty ranges replaced by 1 so they do not scale the domination amount.
+fn objective_ranges<S>(archive: &ParetoArchive<S>, a: &[f64], b: &[f64]) -> Vec<f64> {
+    (0..a.len())
+        .map(|m| {
+            let values = archive
+                .points()
+                .iter()
+                .map(|point| point.objectives[m])
+                .chain([a[m], b[m]]);
+            let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), v| {
+                (low.min(v), high.max(v))
+            });
+            if high > low {
+                high - low
+            } else {
+                1.0
+            }
+        })
+        .collect()
+}
+
+/// The amount by which `a` dominates `b`, normalized by the objective ranges.
+fn domination_amount(a: &[f64], b: &[f64], ranges: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .zip(ranges)
+        .filter(|((x, y), _)| x != y)
+        .map(|((x, y), range)| (x - y).abs() / range)
+        .product()
+}
+
+/// The probability 1 / (1 + exp(d / T)) of accepting a dominated move.
+fn acceptance_probability(amount: f64, temperature: f64) -> f64 {
+    1.0 / (1.0 + (amount / temperature).exp())
+}
@@ -0,0 +1,291 @@
+//! Deterministic parallel multi-start runner.
+//!
+//! Running many independent annealing runs from different initial states and
+//! keeping the best is a simple and robust way to improve solution quality.
+//! This module distributes the runs over a pool of threads while keeping the
+//! results reproducible for a given base seed.
+
+use crate::core::annealer::{Annealer, AnnealingResult};
+use crate::core::energy::Energy;
+use crate::core::schedule::Schedule;
+use crate::core::state::MoveState;
+use crate::rng::seeded_rng::derived_rng;
+use crate::utils::{average, standard_deviation};
+use rand::rngs::StdRng;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Results from a multi-start run.
+#[derive(Clone)]
+pub struct MultiStartResult<S: MoveState> {
+    /// The result of the run that found the best energy
+    pub best: AnnealingResult<S>,
+    /// The index of the run that found the best energy
+    pub best_run: usize,
+    /// The best energy found by each run, in run order
+    pub best_energies: Vec<f64>,
+}
+
+impl<S: MoveState> MultiStartResult<S> {
+    /// Returns the mean of the best energies across runs.
+    pub fn mean_energy(&self) -> f64 {
+        average(&self.best_energies)
+    }
+
+    /// Returns the sample standard deviation of the best energies across runs.
+    pub fn energy_std_dev(&self) -> f64 {
+        standard_deviation(&self.best_energies)
+    }
+}
+
+impl<S: MoveState> fmt::Debug for MultiStartResult<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiStartResult")
+            .field("best", &self.best)
+            .field("best_run", &self.best_run)
+            .field("mean_energy", &self.mean_energy())
+            .field("energy_std_dev", &self.energy_std_dev())
+            .finish()
+    }
+}
+
+/// A hook that customizes each run's annealer before it starts.
+type Setup<E, Sch> = Box<
+    dyn Fn(Annealer<<E as Energy>::State, E, Sch>) -> Annealer<<E as Energy>::State, E, Sch>
+        + Send
+        + Sync,
+>;
+
+/// Runs many independent annealing runs in parallel and keeps the best.
+///
+/// Run `i` uses the random stream `derived_rng(seed, i)`, which is first
+/// handed to the initial-state factory and then to the run's `Annealer`.
+/// Runs are distributed dynamically across a pool of threads, but since each
+/// run only depends on its own stream the results are identical for a given
+/// seed regardless of the number of threads.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::multistart::MultiStart;
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Point(f64);
+///
+/// impl State for Point {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         Point(self.0 + rng.gen_range(-0.1..0.1))
+///     }
+/// }
+///
+/// #[derive(Clone)]
+/// struct Square;
+///
+/// impl Energy for Square {
+///     type State = Point;
+///
+///     fn cost(&self, state: &Point) -> f64 {
+///         state.0 * state.0
+///     }
+/// }
+///
+/// let runner = MultiStart::new(Square, GeometricSchedule::new(1.0, 0.99), 1000, 8, 42);
+///
+/// // Start each run from a random point
+/// let result = runner.run(|_run, rng| Point(rng.gen_range(-10.0..10.0)));
+///
+/// assert_eq!(result.best_energies.len(), 8);
+/// assert_eq!(result.best.best_energy, result.best_energies[result.best_run]);
+/// ```
+pub struct MultiStart<E, Sch>
+where
+    E: Energy + Clone + Send + Sync,
+    Sch: Schedule + Clone,
+{
+    energy: E,
+    schedule: Sch,
+    max_iters: usize,
+    runs: usize,
+    seed: u64,
+    threads: usize,
+    setup: Option<Setup<E, Sch>>,
+}
+
+impl<E, Sch> MultiStart<E, Sch>
+where
+    E: Energy + Clone + Send + Sync,
+    Sch: Schedule + Clone,
+{
+    /// Creates a new multi-start runner.
+    ///
+    /// By default the runs are spread across as many threads as the machine provides.
+    ///
+    /// # Parameters
+    ///
+    /// * `energy`: The energy function, cloned for every run
+    /// * `schedule`: The cooling schedule, cloned for every run
+    /// * `max_iters`: The maximum number of iterations of each run
+    /// * `runs`: The number of independent runs (must be positive)
+    /// * `seed`: The base seed from which each run's random stream is derived
+    ///
+    /// # Panics
+    ///
+    /// Panics if `runs` is zero.
+    pub fn new(energy: E, schedule: Sch, max_iters: usize, runs: usize, seed: u64) -> Self {
+        assert!(runs > 0, "At least one run is required");
+
+        let threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Self {
+            energy,
+            schedule,
+            max_iters,
+            runs,
+            seed,
+            threads,
+            setup: None,
+        }
+    }
+
+    /// Sets the number of threads in the pool.
+    ///
+    /// The results do not depend on this setting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "Thread count must be positive");
+        self.threads = threads;
+        self
+    }
+
+    /// Sets a hook that customizes every run's annealer before it starts.
+    ///
+    /// Use it to register termination criteria, reheating policies or
+    /// observers on each run.
+    ///
+    /// # Parameters
+    ///
+    /// * `setup`: A function receiving the freshly created annealer and returning it configured
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::multistart::MultiStart;
+    /// use frostfire::core::termination::NoImprovement;
+    /// use frostfire::prelude::*;
+    ///
+    /// # #[derive(Clone)]
+    /// # struct MyState;
+    /// # impl State for MyState {
+    /// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+    /// # }
+    /// # #[derive(Clone)]
+    /// # struct MyEnergy;
+    /// # impl Energy for MyEnergy {
+    /// #     type State = MyState;
+    /// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+    /// # }
+    /// let runner = MultiStart::new(MyEnergy, GeometricSchedule::new(10.0, 0.99), 100_000, 4, 7)
+    ///     .with_setup(|annealer| annealer.with_termination(NoImprovement::new(500)));
+    ///
+    /// let result = runner.run(|_, _| MyState);
+    /// assert_eq!(result.best.iterations, 500);
+    /// ```
+    pub fn with_setup<F>(mut self, setup: F) -> Self
+    where
+        F: Fn(Annealer<E::State, E, Sch>) -> Annealer<E::State, E, Sch> + Send + Sync + 'static,
+    {
+        self.setup = Some(Box::new(setup));
+        self
+    }
+
+    /// Performs all runs and collects the best result.
+    ///
+    /// # Parameters
+    ///
+    /// * `factory`: Creates the initial state of a run from its index and random stream
+    ///
+    /// # Returns
+    ///
+    /// A `MultiStartResult` with the best run's result and the best energy of every run.
+    pub fn run<F>(&self, factory: F) -> MultiStartResult<E::State>
+    where
+        F: Fn(usize, &mut StdRng) -> E::State + Sync,
+    {
+        let next_run = AtomicUsize::new(0);
+        let workers = self.threads.min(self.runs);
+
+        let mut results: Vec<(usize, AnnealingResult<E::State>)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut finished = Vec::new();
+                        loop {
+                            let run = next_run.fetch_add(1, Ordering::Relaxed);
+                            if run >= self.runs {
+                                break;
+                            }
+                            finished.push((run, self.single_run(run, &factory)));
+                        }
+                        finished
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("annealing run panicked"))
+                .collect()
+        });
+
+        results.sort_by_key(|(run, _)| *run);
+        let best_energies: Vec<f64> = results.iter().map(|(_, r)| r.best_energy).collect();
+
+        // Ties go to the lowest run index, keeping the choice deterministic
+        let (best_run, best) = results
+            .into_iter()
+            .reduce(|best, candidate| {
+                if candidate.1.best_energy < best.1.best_energy {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .expect("at least one run");
+
+        MultiStartResult {
+            best,
+            best_run,
+            best_energies,
+        }
+    }
+
+    /// Performs a single run with its own derived random stream.
+    fn single_run<F>(&self, run: usize, factory: &F) -> AnnealingResult<E::State>
+    where
+        F: Fn(usize, &mut StdRng) -> E::State,
+    {
+        let mut rng = derived_rng(self.seed, run as u64);
+        let initial_state = factory(run, &mut rng);
+
+        let mut annealer = Annealer::new(
+            initial_state,
+            self.energy.clone(),
+            self.schedule.clone(),
+            rng,
+            self.max_iters,
+        );
+        if let Some(setup) = &self.setup {
+            annealer = setup(annealer);
+        }
+
+        annealer.run_with_stats()
+    }
+}
@@ -0,0 +1,376 @@
+//! Observation hooks for the annealing loop.
+//!
+//! This module provides the `Observer` trait, which lets callers watch an
+//! `Annealer` while it runs for progress reporting, tracing or custom
+//! instrumentation, without reimplementing the annealing loop.
+
+use crate::core::annealer::AnnealingResult;
+use crate::core::state::MoveState;
+use log::info;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A summary of a single annealing iteration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IterationRecord {
+    /// The iteration number (0-based)
+    pub iteration: usize,
+    /// The temperature at which the move was evaluated
+    pub temperature: f64,
+    /// The energy of the current state after the acceptance decision
+    pub current_energy: f64,
+    /// The best energy found so far
+    pub best_energy: f64,
+    /// Whether the proposed move was accepted
+    pub accepted: bool,
+}
+
+/// The `Observer` trait receives callbacks from the annealing loop.
+///
+/// All methods have empty default implementations, so implementors only
+/// override the events they care about. Observers are registered with
+/// `Annealer::with_observer` and are invoked in registration order.
+///
+/// `on_start` fires once when a run starts, or `on_resume` when a run loaded
+/// from a checkpoint continues. Within an iteration the callbacks
+/// fire in this order: `on_accept` and `on_new_best` (if applicable),
+/// `on_iteration`, then `on_temperature_change`. `on_finish` fires once at the
+/// end of a run.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+/// use std::sync::{Arc, Mutex};
+///
+/// # #[derive(Clone)]
+/// # struct MyState(f64);
+/// # impl State for MyState {
+/// #     fn neighbor(&self, rng: &mut impl rand::Rng) -> Self { MyState(self.0 + rng.gen_range(-1.0..1.0)) }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, state: &Self::State) -> f64 { state.0 * state.0 }
+/// # }
+/// // Count how many times a new best state was found
+/// #[derive(Default)]
+/// struct BestCounter(usize);
+///
+/// impl Observer<MyState> for BestCounter {
+///     fn on_new_best(&mut self, _iteration: usize, _state: &MyState, _energy: f64) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let counter = Arc::new(Mutex::new(BestCounter::default()));
+/// let mut annealer = Annealer::new(
+///     MyState(10.0),
+///     MyEnergy,
+///     GeometricSchedule::new(10.0, 0.99),
+///     seeded_rng(42),
+///     1000,
+/// )
+/// .with_observer(counter.clone());
+///
+/// annealer.run();
+/// assert!(counter.lock().unwrap().0 > 0);
+/// ```
+pub trait Observer<S: MoveState>: Send {
+    /// Called once when a run starts, before the first iteration.
+    ///
+    /// Runs resumed from a checkpoint call `on_resume` instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `state`: The initial state, which is also the initial best state
+    /// * `energy`: The energy of `state`
+    fn on_start(&mut self, _state: &S, _energy: f64) {}
+
+    /// Called once when a run loaded from a checkpoint continues, before its
+    /// next iteration.
+    ///
+    /// # Parameters
+    ///
+    /// * `iteration`: The number of iterations completed before the checkpoint
+    /// * `best_state`: The best state restored from the checkpoint
+    /// * `best_energy`: The energy of `best_state`
+    fn on_resume(&mut self, _iteration: usize, _best_state: &S, _best_energy: f64) {}
+
+    /// Called after every iteration, once the acceptance decision is made.
+    ///
+    /// # Parameters
+    ///
+    /// * `record`: A summary of the iteration
+    fn on_iteration(&mut self, _record: &IterationRecord) {}
+
+    /// Called when a proposed move is accepted.
+    ///
+    /// # Parameters
+    ///
+    /// * `iteration`: The current iteration number
+    /// * `state`: The state after applying the accepted move
+    /// * `energy`: The energy of `state`
+    fn on_accept(&mut self, _iteration: usize, _state: &S, _energy: f64) {}
+
+    /// Called when a new best state is recorded.
+    ///
+    /// # Parameters
+    ///
+    /// * `iteration`: The current iteration number
+    /// * `state`: The new best state
+    /// * `energy`: The energy of `state`
+    fn on_new_best(&mut self, _iteration: usize, _state: &S, _energy: f64) {}
+
+    /// Called when the temperature changes between iterations.
+    ///
+    /// # Parameters
+    ///
+    /// * `iteration`: The iteration that just completed
+    /// * `old_temp`: The temperature used during that iteration
+    /// * `new_temp`: The temperature for the next iteration
+    fn on_temperature_change(&mut self, _iteration: usize, _old_temp: f64, _new_temp: f64) {}
+
+    /// Called once when the run completes.
+    ///
+    /// # Parameters
+    ///
+    /// * `result`: The result of the run
+    fn on_finish(&mut self, _result: &AnnealingResult<S>) {}
+}
+
+/// Shared observers let callers inspect the collected data after the run.
+impl<S: MoveState, O: Observer<S>> Observer<S> for Arc<Mutex<O>> {
+    fn on_start(&mut self, state: &S, energy: f64) {
+        self.lock().unwrap().on_start(state, energy);
+    }
+
+    fn on_resume(&mut self, iteration: usize, best_state: &S, best_energy: f64) {
+        self.lock()
+            .unwrap()
+            .on_resume(iteration, best_state, best_energy);
+    }
+
+    fn on_iteration(&mut self, record: &IterationRecord) {
+        self.lock().unwrap().on_iteration(record);
+    }
+
+    fn on_accept(&mut self, iteration: usize, state: &S, energy: f64) {
+        self.lock().unwrap().on_accept(iteration, state, energy);
+    }
+
+    fn on_new_best(&mut self, iteration: usize, state: &S, energy: f64) {
+        self.lock().unwrap().on_new_best(iteration, state, energy);
+    }
+
+    fn on_temperature_change(&mut self, iteration: usize, old_temp: f64, new_temp: f64) {
+        self.lock()
+            .unwrap()
+            .on_temperature_change(iteration, old_temp, new_temp);
+    }
+
+    fn on_finish(&mut self, result: &AnnealingResult<S>) {
+        self.lock().unwrap().on_finish(result);
+    }
+}
+
+/// An observer that reports progress through the `log` crate.
+///
+/// Every `interval` iterations it logs the temperature, current energy and
+/// best energy at `info` level, and it logs a summary when the run finishes.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::observer::ProgressLogger;
+///
+/// // Log progress every 1000 iterations
+/// let logger = ProgressLogger::new(1000);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProgressLogger {
+    interval: usize,
+}
+
+impl ProgressLogger {
+    /// Creates a new progress logger.
+    ///
+    /// # Parameters
+    ///
+    /// * `interval`: The number of iterations between log lines (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn new(interval: usize) -> Self {
+        assert!(interval > 0, "Logging interval must be positive");
+        Self { interval }
+    }
+}
+
+impl<S: MoveState> Observer<S> for ProgressLogger {
+    fn on_iteration(&mut self, record: &IterationRecord) {
+        if (record.iteration + 1).is_multiple_of(self.interval) {
+            info!(
+                "iteration {}: temperature = {:.6}, current energy = {:.6}, best energy = {:.6}",
+                record.iteration + 1,
+                record.temperature,
+                record.current_energy,
+                record.best_energy
+            );
+        }
+    }
+
+    fn on_finish(&mut self, result: &AnnealingResult<S>) {
+        info!(
+            "finished after {} iterations: best energy = {:.6}, accepted {} / rejected {}",
+            result.iterations, result.best_energy, result.accepted_moves, result.rejected_moves
+        );
+    }
+}
+
+/// A shared handle to the best solution of a run in progress.
+///
+/// Register a clone with `Annealer::with_observer` and keep another to read
+/// the best energy and a snapshot of the best state from any thread while the
+/// run continues. Cloning the handle is cheap; all clones share the same data.
+///
+/// The best energy can be read without locking. The best state is copied
+/// into the handle on every improvement, reusing its allocation.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::observer::SharedBest;
+/// use frostfire::prelude::*;
+/// use std::thread;
+///
+/// # #[derive(Clone)]
+/// # struct MyState(f64);
+/// # impl State for MyState {
+/// #     fn neighbor(&self, rng: &mut impl rand::Rng) -> Self { MyState(self.0 + rng.gen_range(-1.0..1.0)) }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, state: &Self::State) -> f64 { state.0 * state.0 }
+/// # }
+/// let best = SharedBest::new();
+/// let mut annealer = Annealer::new(
+///     MyState(10.0),
+///     MyEnergy,
+///     GeometricSchedule::new(10.0, 0.99),
+///     seeded_rng(42),
+///     100_000,
+/// )
+/// .with_observer(best.clone());
+///
+/// let run = thread::spawn(move || annealer.run_with_stats());
+///
+/// // Poll the best solution while the run is in progress
+/// if let Some((state, energy)) = best.snapshot() {
+///     println!("Best so far: x = {}, energy = {}", state.0, energy);
+/// }
+///
+/// let result = run.join().unwrap();
+/// assert_eq!(best.best_energy(), Some(result.best_energy));
+/// ```
+pub struct SharedBest<S> {
+    inner: Arc<SharedBestInner<S>>,
+}
+
+struct SharedBestInner<S> {
+    /// The bits of the best energy, or of NaN before the run starts
+    energy: AtomicU64,
+    /// The best state together with its energy
+    best: Mutex<Option<(S, f64)>>,
+}
+
+impl<S> SharedBest<S> {
+    /// Creates an empty handle, which is filled once a run starts.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(SharedBestInner {
+                energy: AtomicU64::new(f64::NAN.to_bits()),
+                best: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Returns the best energy found so far, without locking.
+    ///
+    /// # Returns
+    ///
+    /// The best energy, or `None` if no run has started yet.
+    pub fn best_energy(&self) -> Option<f64> {
+        let energy = f64::from_bits(self.inner.energy.load(Ordering::Acquire));
+        (!energy.is_nan()).then_some(energy)
+    }
+
+    /// Stores a new best state and energy.
+    fn record(&self, state: &S, energy: f64)
+    where
+        S: Clone,
+    {
+        let mut best = self.inner.best.lock().unwrap();
+        match best.as_mut() {
+            Some((best_state, best_energy)) => {
+                best_state.clone_from(state);
+                *best_energy = energy;
+            }
+            None => *best = Some((state.clone(), energy)),
+        }
+        self.inner.energy.store(energy.to_bits(), Ordering::Release);
+    }
+}
+
+impl<S: Clone> SharedBest<S> {
+    /// Returns a copy of the best state found so far.
+    ///
+    /// # Returns
+    ///
+    /// The best state, or `None` if no run has started yet.
+    pub fn best_state(&self) -> Option<S> {
+        self.snapshot().map(|(state, _)| state)
+    }
+
+    /// Returns a copy of the best state together with its energy.
+    ///
+    /// Unlike separate calls to `best_state` and `best_energy`, the pair is
+    /// guaranteed to be consistent.
+    ///
+    /// # Returns
+    ///
+    /// The best state and its energy, or `None` if no run has started yet.
+    pub fn snapshot(&self) -> Option<(S, f64)> {
+        self.inner.best.lock().unwrap().clone()
+    }
+}
+
+impl<S> Clone for SharedBest<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<S> Default for SharedBest<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: MoveState> Observer<S> for SharedBest<S> {
+    fn on_start(&mut self, state: &S, energy: f64) {
+        self.record(state, energy);
+    }
+
+    fn on_resume(&mut self, _iteration: usize, best_state: &S, best_energy: f64) {
+        self.record(best_state, best_energy);
+    }
+
+    fn on_new_best(&mut self, _iteration: usize, state: &S, energy: f64) {
+        self.record(state, energy);
+    }
+}
@@ -0,0 +1,490 @@
+//! Population annealing engine.
+//!
+//! Population annealing anneals a large population of states together. At
+//! each temperature step the population is resampled with Boltzmann weights,
+//! so members in low-energy regions are copied and members in high-energy
+//! regions die out. Besides finding good optima, the resampling weights give
+//! an estimate of the free energy at every temperature.
+
+use crate::core::energy::Energy;
+use crate::core::state::MoveState;
+use crate::core::transition;
+use crate::rng::seeded_rng::derived_rng;
+use rand::Rng;
+use std::fmt;
+use std::thread;
+
+/// Statistics of one temperature step of a population annealing run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PopulationStep {
+    /// The temperature of the step
+    pub temperature: f64,
+    /// The effective population size of the resampling weights that moved
+    /// the population to this temperature, 1 / sum(w_i^2) for normalized
+    /// weights (the population size at the first temperature)
+    pub effective_size: f64,
+    /// The logarithm of the mean resampling weight, ln(Z(T) / Z(T_previous))
+    /// (zero at the first temperature)
+    pub log_weight_mean: f64,
+    /// The mean energy of the population after the Metropolis sweeps
+    pub mean_energy: f64,
+    /// The lowest energy in the population after the Metropolis sweeps
+    pub min_energy: f64,
+    /// The fraction of accepted Metropolis moves at this temperature
+    pub acceptance_rate: f64,
+    /// The number of families (descendants of distinct initial members) still alive
+    pub families: usize,
+    /// The entropy of the family-size distribution, -sum(f ln f) over the
+    /// fraction f of the population in each family; exp(entropy) is the
+    /// effective number of families
+    pub family_entropy: f64,
+}
+
+/// Results from a population annealing run.
+#[derive(Clone)]
+pub struct PopulationResult<S: MoveState> {
+    /// The best state found by any member
+    pub best_state: S,
+    /// The energy (cost) of the best state
+    pub best_energy: f64,
+    /// The final population
+    pub states: Vec<S>,
+    /// The energy of each member of the final population
+    pub energies: Vec<f64>,
+    /// Statistics for each temperature, in the order they were visited
+    pub steps: Vec<PopulationStep>,
+    /// The estimate of ln(Z(T_final) / Z(T_initial)), the sum of the log mean weights
+    pub log_partition_ratio: f64,
+}
+
+impl<S: MoveState> PopulationResult<S> {
+    /// Estimates the free energy F = -T ln Z at each temperature.
+    ///
+    /// Population annealing only measures ratios of partition functions, so
+    /// the partition function at the initial temperature must be supplied.
+    /// For a finite state space and an initial temperature far above the
+    /// energy scale, ln Z(T_initial) is close to the logarithm of the number
+    /// of states.
+    ///
+    /// # Parameters
+    ///
+    /// * `log_z_initial`: The logarithm of the partition function at the initial temperature
+    ///
+    /// # Returns
+    ///
+    /// The free energy estimate at each temperature, in the order of `steps`.
+    pub fn free_energies(&self, log_z_initial: f64) -> Vec<f64> {
+        let mut log_z = log_z_initial;
+        self.steps
+            .iter()
+            .map(|step| {
+                log_z += step.log_weight_mean;
+                -step.temperature * log_z
+            })
+            .collect()
+    }
+}
+
+impl<S: MoveState> fmt::Debug for PopulationResult<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PopulationResult")
+            .field("best_energy", &self.best_energy)
+            .field("population", &self.states.len())
+            .field("steps", &self.steps.len())
+            .field("log_partition_ratio", &self.log_partition_ratio)
+            .finish()
+    }
+}
+
+/// A member of the population.
+#[derive(Clone)]
+struct Member<S> {
+    state: S,
+    energy: f64,
+    /// The index of the initial member this one descends from
+    family: usize,
+}
+
+/// The outcome of the Metropolis sweeps of some members.
+struct Sweep<S> {
+    /// The number of accepted moves
+    accepted: usize,
+    /// The best state visited and its energy, if it improves on the best so far
+    best: Option<(S, f64)>,
+}
+
+/// Population annealing engine, annealing many copies of a state together.
+///
+/// The population starts as `population` copies of the initial state. At each
+/// temperature of the (strictly decreasing) schedule, every member runs
+/// `sweeps` Metropolis steps, in parallel across threads. When moving from
+/// inverse temperature beta to beta', member i is weighted by
+///
+/// w_i = exp(-(beta' - beta) * E_i)
+///
+/// and the population is resampled in proportion to these weights with
+/// systematic resampling, which keeps its size fixed. The mean weight
+/// estimates Z(beta') / Z(beta), and the spread of the weights is reported
+/// as the effective population size.
+///
+/// Each member's Metropolis steps draw from a stream derived from the seed
+/// with `derived_rng`, and resampling has its own stream, so results are
+/// identical for a given seed regardless of the number of threads.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Point(f64);
+///
+/// impl State for Point {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         Point(self.0 + rng.gen_range(-0.5..0.5))
+///     }
+/// }
+///
+/// // A double well with its global minimum near x = -1
+/// struct DoubleWell;
+///
+/// impl Energy for DoubleWell {
+///     type State = Point;
+///
+///     fn cost(&self, state: &Point) -> f64 {
+///         (state.0 * state.0 - 1.0).powi(2) + 0.3 * state.0
+///     }
+/// }
+///
+/// // 30 temperatures from 2.0 down to about 0.02
+/// let temperatures: Vec<f64> = (0..30).map(|k| 2.0 * 0.85_f64.powi(k)).collect();
+///
+/// let result = PopulationAnnealing::new(Point(1.0), DoubleWell, temperatures, 200, 42)
+///     .with_sweeps(20)
+///     .run();
+///
+/// assert!(result.best_state.0 < 0.0);
+/// for step in &result.steps {
+///     println!(
+///         "T = {:.3}: ESS = {:.1}, families = {}",
+///         step.temperature, step.effective_size, step.families
+///     );
+/// }
+/// ```
+pub struct PopulationAnnealing<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S> + Sync,
+{
+    initial_state: S,
+    energy: E,
+    temperatures: Vec<f64>,
+    population: usize,
+    seed: u64,
+    sweeps: usize,
+    threads: usize,
+}
+
+impl<S, E> PopulationAnnealing<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S> + Sync,
+{
+    /// Creates a new population annealing engine.
+    ///
+    /// By default every member runs 10 Metropolis steps per temperature,
+    /// using as many threads as the machine provides.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_state`: The starting state of every member
+    /// * `energy`: The energy function to be minimized
+    /// * `temperatures`: The temperature schedule (positive and strictly decreasing)
+    /// * `population`: The number of members (must be positive)
+    /// * `seed`: The base seed from which all random streams are derived
+    ///
+    /// # Panics
+    ///
+    /// Panics if `temperatures` is empty, not positive, or not strictly
+    /// decreasing, or if `population` is zero.
+    pub fn new(
+        initial_state: S,
+        energy: E,
+        temperatures: Vec<f64>,
+        population: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(
+            !temperatures.is_empty(),
+            "At least one temperature is required"
+        );
+        assert!(
+            temperatures.iter().all(|&t| t > 0.0),
+            "Temperatures must be positive"
+        );
+        assert!(
+            temperatures.windows(2).all(|pair| pair[0] > pair[1]),
+            "Temperatures must be strictly decreasing"
+        );
+        assert!(population > 0, "Population size must be positive");
+
+        let threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Self {
+            initial_state,
+            energy,
+            temperatures,
+            population,
+            seed,
+            sweeps: 10,
+            threads,
+        }
+    }
+
+    /// Sets the number of Metropolis steps each member runs at every temperature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sweeps` is zero.
+    pub fn with_sweeps(mut self, sweeps: usize) -> Self {
+        assert!(sweeps > 0, "Sweep count must be positive");
+        self.sweeps = sweeps;
+        self
+    }
+
+    /// Sets the number of threads used to advance the population.
+    ///
+    /// The results do not depend on this setting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "Thread count must be positive");
+        self.threads = threads;
+        self
+    }
+
+    /// Runs population annealing through every temperature of the schedule.
+    ///
+    /// # Returns
+    ///
+    /// A `PopulationResult` with the best state found, the final population
+    /// and the statistics of each temperature step.
+    pub fn run(&self) -> PopulationResult<S> {
+        let initial_energy = self.energy.cost(&self.initial_state);
+        let mut members: Vec<Member<S>> = (0..self.population)
+            .map(|family| Member {
+                state: self.initial_state.clone(),
+                energy: initial_energy,
+                family,
+            })
+            .collect();
+
+        let mut best_state = self.initial_state.clone();
+        let mut best_energy = initial_energy;
+
+        // Stream 0 is for resampling; the members' streams follow
+        let mut resample_rng = derived_rng(self.seed, 0);
+        let mut steps = Vec::with_capacity(self.temperatures.len());
+        let mut log_partition_ratio = 0.0;
+
+        for (k, &temperature) in self.temperatures.iter().enumerate() {
+            let (effective_size, log_weight_mean) = if k == 0 {
+                (self.population as f64, 0.0)
+            } else {
+                let previous = self.temperatures[k - 1];
+                let delta_beta = 1.0 / temperature - 1.0 / previous;
+                Self::resample(&mut members, delta_beta, &mut resample_rng)
+            };
+            log_partition_ratio += log_weight_mean;
+
+            let sweep = self.advance_members(&mut members, k, temperature, best_energy);
+            if let Some((state, energy)) = sweep.best {
+                best_state = state;
+                best_energy = energy;
+            }
+
+            let (families, family_entropy) = self.family_diversity(&members);
+            steps.push(PopulationStep {
+                temperature,
+                effective_size,
+                log_weight_mean,
+                mean_energy: members.iter().map(|m| m.energy).sum::<f64>() / self.population as f64,
+                min_energy: members
+                    .iter()
+                    .map(|m| m.energy)
+                    .fold(f64::INFINITY, f64::min),
+                acceptance_rate: sweep.accepted as f64 / (self.population * self.sweeps) as f64,
+                families,
+                family_entropy,
+            });
+        }
+
+        PopulationResult {
+            best_state,
+            best_energy,
+            energies: members.iter().map(|m| m.energy).collect(),
+            states: members.into_iter().map(|m| m.state).collect(),
+            steps,
+            log_partition_ratio,
+        }
+    }
+
+    /// Resamples the population with weights exp(-delta_beta * E).
+    ///
+    /// # Returns
+    ///
+    /// The effective population size of the weights and the log mean weight.
+    fn resample(members: &mut Vec<Member<S>>, delta_beta: f64, rng: &mut impl Rng) -> (f64, f64) {
+        let size = members.len();
+
+        // Weights relative to the lowest energy, so the largest weight is 1
+        let min_energy = members
+            .iter()
+            .map(|m| m.energy)
+            .fold(f64::INFINITY, f64::min);
+        let weights: Vec<f64> = members
+            .iter()
+            .map(|m| (-delta_beta * (m.energy - min_energy)).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        let log_weight_mean = -delta_beta * min_energy + (total / size as f64).ln();
+        let effective_size = total * total / weights.iter().map(|w| w * w).sum::<f64>();
+
+        // Systematic resampling: one uniform offset, then evenly spaced pointers
+        let spacing = total / size as f64;
+        let mut pointer = rng.gen::<f64>() * spacing;
+        let mut cumulative = 0.0;
+        let mut resampled = Vec::with_capacity(size);
+        for (member, weight) in members.iter().zip(&weights) {
+            cumulative += weight;
+            while pointer < cumulative && resampled.len() < size {
+                resampled.push(member.clone());
+                pointer += spacing;
+            }
+        }
+        // Rounding can leave the last pointers just past the final sum
+        while resampled.len() < size {
+            resampled.push(members[size - 1].clone());
+        }
+
+        *members = resampled;
+        (effective_size, log_weight_mean)
+    }
+
+    /// Runs the Metropolis sweeps of every member, spreading them across threads.
+    ///
+    /// # Parameters
+    ///
+    /// * `members`: The population
+    /// * `step`: The index of the temperature
+    /// * `temperature`: The temperature of the sweeps
+    /// * `best_energy`: The best energy found before these sweeps
+    ///
+    /// # Returns
+    ///
+    /// The number of accepted moves, and the best state visited during the
+    /// sweeps if it improves on `best_energy`.
+    fn advance_members(
+        &self,
+        members: &mut [Member<S>],
+        step: usize,
+        temperature: f64,
+        best_energy: f64,
+    ) -> Sweep<S> {
+        let energy = &self.energy;
+        let sweeps = self.sweeps;
+        let population = self.population as u64;
+        let seed = self.seed;
+
+        let advance = |offset: usize, chunk: &mut [Member<S>]| -> Sweep<S> {
+            let mut sweep = Sweep {
+                accepted: 0,
+                best: None,
+            };
+            let mut threshold = best_energy;
+            for (i, member) in chunk.iter_mut().enumerate() {
+                let stream = 1 + step as u64 * population + (offset + i) as u64;
+                let mut rng = derived_rng(seed, stream);
+                for _ in 0..sweeps {
+                    if transition::metropolis_step(
+                        energy,
+                        &mut member.state,
+                        &mut member.energy,
+                        temperature,
+                        &mut rng,
+                    ) {
+                        sweep.accepted += 1;
+                        // Keep states that are left again before the sweeps end
+                        if member.energy < threshold {
+                            threshold = member.energy;
+                            sweep.best = Some((member.state.clone(), member.energy));
+                        }
+                    }
+                }
+            }
+            sweep
+        };
+
+        if self.threads == 1 || members.len() == 1 {
+            return advance(0, members);
+        }
+
+        let chunk_size = members.len().div_ceil(self.threads);
+        thread::scope(|scope| {
+            let handles: Vec<_> = members
+                .chunks_mut(chunk_size)
+                .enumerate()
+                .map(|(c, chunk)| {
+                    let advance = &advance;
+                    scope.spawn(move || advance(c * chunk_size, chunk))
+                })
+                .collect();
+            // Combine in chunk order, so ties go to the lowest member index
+            // regardless of the number of threads
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("population worker panicked"))
+                .fold(
+                    Sweep {
+                        accepted: 0,
+                        best: None,
+                    },
+                    |mut total, sweep| {
+                        total.accepted += sweep.accepted;
+                        if let Some((state, energy)) = sweep.best {
+                            if total.best.as_ref().is_none_or(|best| energy < best.1) {
+                                total.best = Some((state, energy));
+                            }
+                        }
+                        total
+                    },
+                )
+        })
+    }
+
+    /// Computes the number of surviving families and the family-size entropy.
+    fn family_diversity(&self, members: &[Member<S>]) -> (usize, f64) {
+        let mut sizes = vec![0usize; self.population];
+        for member in members {
+            sizes[member.family] += 1;
+        }
+
+        let total = members.len() as f64;
+        let families = sizes.iter().filter(|&&size| size > 0).count();
+        let entropy = sizes
+            .iter()
+            .filter(|&&size| size > 0)
+            .map(|&size| {
+                let fraction = size as f64 / total;
+                -fraction * fraction.ln()
+            })
+            .sum();
+        (families, entropy)
+    }
+}
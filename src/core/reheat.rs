@@ -0,0 +1,163 @@
+//! Reheating and restart strategies for simulated annealing.
+//!
+//! Long runs on rugged landscapes can freeze in a basin with no way out.
+//! A `ReheatPolicy` raises the temperature again when the run stagnates, or
+//! periodically, giving the search a chance to escape.
+
+/// When a reheat is triggered.
+#[derive(Clone, Debug)]
+enum Trigger {
+    /// After a number of iterations without improving the best energy
+    Stagnation { patience: usize },
+    /// After a number of iterations since the previous restart, growing by a factor
+    Periodic { period: usize, growth: f64 },
+}
+
+/// A policy describing when and how the `Annealer` reheats.
+///
+/// On a reheat the temperature is raised to a fraction of the schedule's
+/// initial temperature and the iteration counter passed to the schedule starts
+/// again from zero, so iteration-based schedules replay their decay.
+/// Each reheat is counted in `AnnealingResult::reheats`.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::reheat::ReheatPolicy;
+/// use frostfire::prelude::*;
+///
+/// # #[derive(Clone)]
+/// # struct MyState;
+/// # impl State for MyState {
+/// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+/// # }
+/// // Reheat to half the initial temperature after 1000 stagnant iterations
+/// let mut annealer = Annealer::new(
+///     MyState,
+///     MyEnergy,
+///     GeometricSchedule::new(100.0, 0.95),
+///     seeded_rng(42),
+///     10000,
+/// )
+/// .with_reheat(ReheatPolicy::on_stagnation(1000, 0.5));
+///
+/// let result = annealer.run_with_stats();
+/// assert_eq!(result.reheats, 9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReheatPolicy {
+    trigger: Trigger,
+    fraction: f64,
+    restart_from_best: bool,
+}
+
+impl ReheatPolicy {
+    /// Reheats after the best energy has not improved for `patience` iterations.
+    ///
+    /// The stagnation counter restarts after every reheat.
+    ///
+    /// # Parameters
+    ///
+    /// * `patience`: The number of iterations without improvement before reheating (must be positive)
+    /// * `fraction`: The fraction of the initial temperature to reheat to (must be in (0, 1])
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patience` is zero or `fraction` is not in (0, 1].
+    pub fn on_stagnation(patience: usize, fraction: f64) -> Self {
+        assert!(patience > 0, "Patience must be positive");
+        Self::validate_fraction(fraction);
+        Self {
+            trigger: Trigger::Stagnation { patience },
+            fraction,
+            restart_from_best: false,
+        }
+    }
+
+    /// Reheats after stagnation and restarts the search from the best state found so far.
+    ///
+    /// # Parameters
+    ///
+    /// * `patience`: The number of iterations without improvement before restarting (must be positive)
+    /// * `fraction`: The fraction of the initial temperature to reheat to (must be in (0, 1])
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patience` is zero or `fraction` is not in (0, 1].
+    pub fn restart_from_best(patience: usize, fraction: f64) -> Self {
+        Self {
+            restart_from_best: true,
+            ..Self::on_stagnation(patience, fraction)
+        }
+    }
+
+    /// Periodically restarts at the initial temperature, in the style of cosine
+    /// annealing with warm restarts (SGDR).
+    ///
+    /// The first restart happens after `period` iterations, and each following
+    /// period is `growth` times longer than the previous one. Combined with a
+    /// schedule that decays over one period, the temperature follows a sawtooth
+    /// of repeated annealing cycles.
+    ///
+    /// # Parameters
+    ///
+    /// * `period`: The number of iterations before the first restart (must be positive)
+    /// * `growth`: The factor by which each period grows (must be at least 1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero or `growth` is less than 1.
+    pub fn warm_restarts(period: usize, growth: f64) -> Self {
+        assert!(period > 0, "Period must be positive");
+        assert!(growth >= 1.0, "Period growth must be at least 1");
+        Self {
+            trigger: Trigger::Periodic { period, growth },
+            fraction: 1.0,
+            restart_from_best: false,
+        }
+    }
+
+    fn validate_fraction(fraction: f64) {
+        assert!(
+            fraction > 0.0 && fraction <= 1.0,
+            "Reheat fraction must be in (0, 1]"
+        );
+    }
+
+    /// Returns the fraction of the initial temperature to reheat to.
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Returns whether the search restarts from the best state on a reheat.
+    pub fn restarts_from_best(&self) -> bool {
+        self.restart_from_best
+    }
+
+    /// Checks whether a reheat is due.
+    ///
+    /// # Parameters
+    ///
+    /// * `cycle_iterations`: Iterations since the run started or the last reheat
+    /// * `stagnant_iterations`: Iterations since the best energy improved or the last reheat
+    /// * `reheats`: The number of reheats performed so far
+    pub(crate) fn is_due(
+        &self,
+        cycle_iterations: usize,
+        stagnant_iterations: usize,
+        reheats: usize,
+    ) -> bool {
+        match self.trigger {
+            Trigger::Stagnation { patience } => stagnant_iterations >= patience,
+            Trigger::Periodic { period, growth } => {
+                let length = (period as f64 * growth.powi(reheats as i32)).round() as usize;
+                cycle_iterations >= length
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Fixed-temperature Metropolis sampling.
+//!
+//! The same `State` and `Energy` models used for optimization also define a
+//! Boltzmann distribution, P(s) proportional to exp(-E(s) / T). The
+//! `MetropolisSampler` draws correlated samples from this distribution at a
+//! constant temperature and reports how many effectively independent samples
+//! they amount to.
+
+use crate::core::energy::Energy;
+use crate::core::state::MoveState;
+use crate::core::transition;
+use crate::utils::{average, integrated_autocorrelation_time};
+use rand::rngs::StdRng;
+
+/// Summary statistics of an observable recorded along a Markov chain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleStats {
+    /// The mean of the recorded values
+    pub mean: f64,
+    /// The sample variance of the recorded values
+    pub variance: f64,
+    /// The integrated autocorrelation time, in recorded samples
+    pub autocorrelation_time: f64,
+    /// The number of samples divided by the autocorrelation time
+    pub effective_sample_size: f64,
+    /// The standard error of the mean, accounting for autocorrelation
+    pub standard_error: f64,
+}
+
+impl SampleStats {
+    /// Computes the statistics of a series of recorded values.
+    ///
+    /// # Parameters
+    ///
+    /// * `values`: The values of the observable, in the order they were recorded
+    pub fn from_values(values: &[f64]) -> Self {
+        let mean = average(values);
+        let variance = if values.len() < 2 {
+            0.0
+        } else {
+            values.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+        };
+        let autocorrelation_time = integrated_autocorrelation_time(values);
+        let effective_sample_size = values.len() as f64 / autocorrelation_time;
+        let standard_error = if values.is_empty() {
+            0.0
+        } else {
+            (variance / effective_sample_size).sqrt()
+        };
+
+        Self {
+            mean,
+            variance,
+            autocorrelation_time,
+            effective_sample_size,
+            standard_error,
+        }
+    }
+}
+
+/// Samples recorded by a `MetropolisSampler`.
+#[derive(Clone, Debug)]
+pub struct Samples<T> {
+    /// The recorded states or observable values, in chain order
+    pub values: Vec<T>,
+    /// The energy of the state at each recorded sample
+    pub energies: Vec<f64>,
+    /// The temperature the samples were drawn at
+    pub temperature: f64,
+    /// The number of Metropolis steps between consecutive samples
+    pub thinning: usize,
+    /// The fraction of accepted moves while recording (excluding burn-in)
+    pub acceptance_rate: f64,
+}
+
+impl<T> Samples<T> {
+    /// Returns the number of recorded samples.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether no samples were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the statistics of the energy of the recorded samples.
+    pub fn energy_stats(&self) -> SampleStats {
+        SampleStats::from_values(&self.energies)
+    }
+
+    /// Returns the statistics of a scalar observable of the recorded samples.
+    ///
+    /// # Parameters
+    ///
+    /// * `observable`: Maps a recorded value to the scalar to be analyzed
+    pub fn stats(&self, observable: impl Fn(&T) -> f64) -> SampleStats {
+        let values: Vec<f64> = self.values.iter().map(observable).collect();
+        SampleStats::from_values(&values)
+    }
+}
+
+/// Draws samples from the Boltzmann distribution at a fixed temperature.
+///
+/// The sampler runs a single Metropolis chain. The first call to `sample` or
+/// `sample_with` runs `burn_in` steps without recording, so the chain can
+/// forget its initial state; every recorded sample is then `thinning` steps
+/// after the previous one. Further calls continue the same chain.
+///
+/// The acceptance rule is always the Metropolis criterion, which is what
+/// makes the chain sample the Boltzmann distribution, provided the proposal
+/// distribution of the state is symmetric.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Point(f64);
+///
+/// impl State for Point {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         Point(self.0 + rng.gen_range(-1.0..1.0))
+///     }
+/// }
+///
+/// // A harmonic well: at temperature T, x is normally distributed with variance T
+/// struct Harmonic;
+///
+/// impl Energy for Harmonic {
+///     type State = Point;
+///
+///     fn cost(&self, state: &Point) -> f64 {
+///         0.5 * state.0 * state.0
+///     }
+/// }
+///
+/// let mut sampler = MetropolisSampler::new(Point(5.0), Harmonic, 2.0, seeded_rng(42))
+///     .with_burn_in(1000)
+///     .with_thinning(5);
+///
+/// // Record the position instead of the whole state
+/// let samples = sampler.sample_with(20000, |point| point.0);
+/// let stats = samples.stats(|&x| x * x);
+///
+/// assert!((stats.mean - 2.0).abs() < 5.0 * stats.standard_error);
+/// println!(
+///     "<x^2> = {:.3} +/- {:.3} from {:.0} effective samples",
+///     stats.mean, stats.standard_error, stats.effective_sample_size
+/// );
+/// ```
+pub struct MetropolisSampler<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S>,
+{
+    state: S,
+    energy: E,
+    current_energy: f64,
+    temperature: f64,
+    rng: StdRng,
+    burn_in: usize,
+    thinning: usize,
+    burned_in: bool,
+}
+
+impl<S, E> MetropolisSampler<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S>,
+{
+    /// Creates a new sampler.
+    ///
+    /// By default there is no burn-in and every step is recorded.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_state`: The starting state of the chain
+    /// * `energy`: The energy function defining the distribution
+    /// * `temperature`: The sampling temperature (must be positive)
+    /// * `rng`: The random number generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `temperature` is not positive.
+    pub fn new(initial_state: S, energy: E, temperature: f64, rng: StdRng) -> Self {
+        assert!(temperature > 0.0, "Temperature must be positive");
+        let current_energy = energy.cost(&initial_state);
+        Self {
+            state: initial_state,
+            energy,
+            current_energy,
+            temperature,
+            rng,
+            burn_in: 0,
+            thinning: 1,
+            burned_in: false,
+        }
+    }
+
+    /// Sets the number of steps run before the first sample is recorded.
+    pub fn with_burn_in(mut self, burn_in: usize) -> Self {
+        self.burn_in = burn_in;
+        self
+    }
+
+    /// Sets the number of steps between consecutive recorded samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `thinning` is zero.
+    pub fn with_thinning(mut self, thinning: usize) -> Self {
+        assert!(thinning > 0, "Thinning interval must be positive");
+        self.thinning = thinning;
+        self
+    }
+
+    /// Returns the current state of the chain.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns the energy of the current state of the chain.
+    pub fn current_energy(&self) -> f64 {
+        self.current_energy
+    }
+
+    /// Returns the sampling temperature.
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Records copies of the states of the chain.
+    ///
+    /// # Parameters
+    ///
+    /// * `count`: The number of samples to record
+    ///
+    /// # Returns
+    ///
+    /// The recorded states, with their energies and the acceptance rate.
+    pub fn sample(&mut self, count: usize) -> Samples<S> {
+        self.sample_with(count, S::clone)
+    }
+
+    /// Records an observable projected from the states of the chain.
+    ///
+    /// Projecting avoids cloning whole states when only a few quantities are
+    /// of interest.
+    ///
+    /// # Parameters
+    ///
+    /// * `count`: The number of samples to record
+    /// * `observable`: Projects the current state to the recorded value
+    ///
+    /// # Returns
+    ///
+    /// The recorded values, with the energies of their states and the
+    /// acceptance rate.
+    pub fn sample_with<T>(
+        &mut self,
+        count: usize,
+        mut observable: impl FnMut(&S) -> T,
+    ) -> Samples<T> {
+        if !self.burned_in {
+            for _ in 0..self.burn_in {
+                self.step();
+            }
+            self.burned_in = true;
+        }
+
+        let mut values = Vec::with_capacity(count);
+        let mut energies = Vec::with_capacity(count);
+        let mut accepted = 0;
+        for _ in 0..count {
+            for _ in 0..self.thinning {
+                if self.step() {
+                    accepted += 1;
+                }
+            }
+            values.push(observable(&self.state));
+            energies.push(self.current_energy);
+        }
+
+        let steps = count * self.thinning;
+        Samples {
+            values,
+            energies,
+            temperature: self.temperature,
+            thinning: self.thinning,
+            acceptance_rate: if steps == 0 {
+                0.0
+            } else {
+                accepted as f64 / steps as f64
+            },
+        }
+    }
+
+    /// Runs one Metropolis step, returning whether the move was accepted.
+    fn step(&mut self) -> bool {
+        transition::metropolis_step(
+            &self.energy,
+            &mut self.state,
+            &mut self.current_energy,
+            self.temperature,
+            &mut self.rng,
+        )
+    }
+}
@@ -3,6 +3,37 @@
 //! This module provides various cooling schedule implementations that
 //! control how temperature decreases during the annealing process.
 
+use crate::core::checkpoint::{invalid_data, Persist};
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Feedback about a completed annealing iteration, reported to the schedule.
+///
+/// The annealer passes this to `Schedule::observe` after every iteration,
+/// before asking for the next temperature, so schedules can adapt to the
+/// acceptance rate or the energy landscape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Feedback {
+    /// The iteration number (0-based), as passed to `next_temp`
+    pub iteration: usize,
+    /// The temperature at which the move was evaluated
+    pub temperature: f64,
+    /// Whether the proposed move was accepted
+    pub accepted: bool,
+    /// The energy difference of the proposed move, or `NaN` for a tabu move
+    /// rejected without being evaluated
+    pub delta: f64,
+    /// The energy of the current state after the acceptance decision
+    pub current_energy: f64,
+    /// The best energy found so far
+    pub best_energy: f64,
+}
+
 /// The `Schedule` trait defines how temperature decreases during the annealing process.
 ///
 /// A cooling schedule is a crucial component of simulated annealing as it controls
@@ -51,13 +82,47 @@ pub trait Schedule: Send + Sync {
     ///
     /// The next temperature as a positive floating-point value.
     fn next_temp(&self, current_temp: f64, iteration: usize) -> f64;
+
+    /// Receives feedback about the iteration that just completed.
+    ///
+    /// The annealer calls this method once per iteration, right before
+    /// `next_temp`. Adaptive schedules use it to track acceptance rates or
+    /// energy statistics; the default implementation ignores the feedback.
+    ///
+    /// # Parameters
+    ///
+    /// * `feedback`: The outcome of the iteration
+    fn observe(&mut self, _feedback: &Feedback) {}
+
+    /// Writes the schedule's internal state to a checkpoint.
+    ///
+    /// Schedules whose temperatures depend on more than their parameters,
+    /// such as adaptive schedules tracking acceptance history, must save that
+    /// state so a resumed run continues identically. The default
+    /// implementation writes nothing.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: The checkpoint being written
+    fn save_state(&self, _writer: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Restores the internal state written by `save_state`.
+    ///
+    /// # Parameters
+    ///
+    /// * `reader`: The checkpoint being read
+    fn load_state(&mut self, _reader: &mut dyn Read) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 /// A geometric cooling schedule that decreases temperature by a constant factor.
 ///
 /// This is the most commonly used cooling schedule due to its simplicity and effectiveness.
 /// Temperature decreases by multiplying by a constant alpha factor (between 0 and 1)
-/// at each iteration.
+/// at each iteration. It is also known as exponential multiplicative cooling.
 ///
 /// The temperature at iteration k is given by:
 /// T(k) = T(0) * alpha^k
@@ -103,6 +168,60 @@ impl GeometricSchedule {
             alpha,
         }
     }
+
+    /// Creates a geometric schedule that reaches `final_temperature` after a number of iterations.
+    ///
+    /// The cooling rate is alpha = (T_end / T_start)^(1 / iterations). When
+    /// the budget is so long that alpha rounds to 1, the largest rate below 1
+    /// is used instead, and the schedule cools slower than requested.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    /// * `final_temperature`: The temperature after `iterations` iterations (positive, below the initial temperature)
+    /// * `iterations`: The iteration budget (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the temperatures are not positive and decreasing, or if `iterations` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::prelude::*;
+    ///
+    /// let schedule = GeometricSchedule::with_budget(100.0, 0.01, 1000);
+    ///
+    /// let mut temp = schedule.initial_temp();
+    /// for i in 0..1000 {
+    ///     temp = schedule.next_temp(temp, i);
+    /// }
+    /// assert!((temp - 0.01).abs() < 1e-9);
+    /// ```
+    pub fn with_budget(
+        initial_temperature: f64,
+        final_temperature: f64,
+        iterations: usize,
+    ) -> Self {
+        validate_budget(initial_temperature, final_temperature, iterations);
+        let alpha = (final_temperature / initial_temperature)
+            .powf(1.0 / iterations as f64)
+            .min(f64::from_bits(1.0f64.to_bits() - 1));
+        Self::new(initial_temperature, alpha)
+    }
+}
+
+/// Validates the parameters of a schedule decaying over a budget.
+fn validate_budget(initial_temperature: f64, final_temperature: f64, iterations: usize) {
+    assert!(
+        initial_temperature > 0.0,
+        "Initial temperature must be positive"
+    );
+    assert!(
+        final_temperature > 0.0 && final_temperature < initial_temperature,
+        "Final temperature must be positive and below the initial temperature"
+    );
+    assert!(iterations > 0, "Iteration budget must be positive");
 }
 
 impl Schedule for GeometricSchedule {
@@ -179,6 +298,9 @@ impl Schedule for LogarithmicSchedule {
 /// ratio of proposed moves, aiming to maintain a target acceptance ratio
 /// throughout the annealing process.
 ///
+/// When driven by an `Annealer`, every acceptance decision is reported through
+/// `Schedule::observe`, so no manual bookkeeping is needed.
+///
 /// # Examples
 ///
 /// ```
@@ -201,7 +323,7 @@ pub struct AdaptiveSchedule {
     target_acceptance_ratio: f64,
     min_alpha: f64,
     max_alpha: f64,
-    acceptance_history: Vec<bool>,
+    acceptance_history: VecDeque<bool>,
     window_size: usize,
 }
 
@@ -262,7 +384,7 @@ impl AdaptiveSchedule {
             target_acceptance_ratio,
             min_alpha,
             max_alpha,
-            acceptance_history: Vec::new(),
+            acceptance_history: VecDeque::new(),
             window_size: 100, // Use last 100 moves to compute ratio
         }
     }
@@ -275,9 +397,9 @@ impl AdaptiveSchedule {
     ///
     /// * `accepted`: Whether the move was accepted
     pub fn record_acceptance(&mut self, accepted: bool) {
-        self.acceptance_history.push(accepted);
+        self.acceptance_history.push_back(accepted);
         if self.acceptance_history.len() > self.window_size {
-            self.acceptance_history.remove(0);
+            self.acceptance_history.pop_front();
         }
     }
 
@@ -319,4 +441,915 @@ impl Schedule for AdaptiveSchedule {
 
         current_temp * alpha
     }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        self.record_acceptance(feedback.accepted);
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        let history: Vec<bool> = self.acceptance_history.iter().copied().collect();
+        history.write_to(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.acceptance_history = Vec::<bool>::read_from(reader)?.into();
+        Ok(())
+    }
+}
+
+/// The shape of a temperature decay from an initial to a final temperature.
+///
+/// Each shape maps the elapsed fraction of a budget, from 0 to 1, to a
+/// temperature between T_start and T_end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decay {
+    /// T = T_start - (T_start - T_end) * f
+    Linear,
+    /// T = T_start * (T_end / T_start)^f
+    Exponential,
+    /// T = T_end + (T_start - T_end) * (1 + cos(pi * f)) / 2
+    Cosine,
+}
+
+impl Decay {
+    /// Computes the temperature at an elapsed fraction of the budget.
+    fn temperature(self, initial: f64, last: f64, fraction: f64) -> f64 {
+        let fraction = fraction.clamp(0.0, 1.0);
+        match self {
+            Decay::Linear => initial - (initial - last) * fraction,
+            Decay::Exponential => initial * (last / initial).powf(fraction),
+            Decay::Cosine => last + (initial - last) * 0.5 * (1.0 + (PI * fraction).cos()),
+        }
+    }
+}
+
+/// Defines a schedule decaying from T_start to T_end over an iteration budget.
+///
+/// The temperature returned by `next_temp(_, k)` is the one for iteration
+/// k + 1, so the final temperature is reached after exactly `iterations`
+/// iterations and held afterwards.
+macro_rules! budget_schedule {
+    ($(#[$doc:meta])* $name:ident, $decay:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            initial_temperature: f64,
+            final_temperature: f64,
+            iterations: f64,
+        }
+
+        impl $name {
+            /// Creates a new schedule decaying over an iteration budget.
+            ///
+            /// # Parameters
+            ///
+            /// * `initial_temperature`: The starting temperature (must be positive)
+            /// * `final_temperature`: The temperature after `iterations` iterations (positive, below the initial temperature)
+            /// * `iterations`: The iteration budget (must be positive)
+            ///
+            /// # Panics
+            ///
+            /// Panics if the temperatures are not positive and decreasing, or if `iterations` is zero.
+            pub fn new(initial_temperature: f64, final_temperature: f64, iterations: usize) -> Self {
+                validate_budget(initial_temperature, final_temperature, iterations);
+                Self {
+                    initial_temperature,
+                    final_temperature,
+                    iterations: iterations as f64,
+                }
+            }
+        }
+
+        impl Schedule for $name {
+            fn initial_temp(&self) -> f64 {
+                self.initial_temperature
+            }
+
+            fn next_temp(&self, _current_temp: f64, iteration: usize) -> f64 {
+                let fraction = (iteration + 1) as f64 / self.iterations;
+                $decay.temperature(self.initial_temperature, self.final_temperature, fraction)
+            }
+        }
+    };
+}
+
+budget_schedule!(
+    /// A linear cooling schedule that reaches a final temperature at the end of its budget.
+    ///
+    /// The temperature at iteration k of a budget of n iterations is given by:
+    /// T(k) = T_start - (T_start - T_end) * k / n
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::schedule::LinearSchedule;
+    /// use frostfire::prelude::*;
+    ///
+    /// let schedule = LinearSchedule::new(100.0, 10.0, 1000);
+    /// assert_eq!(schedule.next_temp(100.0, 499), 55.0);
+    /// assert_eq!(schedule.next_temp(55.0, 999), 10.0);
+    /// ```
+    LinearSchedule,
+    Decay::Linear
+);
+
+impl LinearSchedule {
+    /// Creates a classic linear schedule that lowers the temperature by a fixed step.
+    ///
+    /// The temperature at iteration k is given by:
+    /// T(k) = max(T_min, T(0) - step * k)
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    /// * `step`: The decrease per iteration (must be positive)
+    /// * `min_temperature`: The floor at which the temperature is held (positive, below the initial temperature)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive or the temperatures are not positive and decreasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::schedule::LinearSchedule;
+    /// use frostfire::prelude::*;
+    ///
+    /// let schedule = LinearSchedule::with_step(10.0, 0.5, 1.0);
+    /// assert!((schedule.next_temp(10.0, 0) - 9.5).abs() < 1e-12);
+    /// assert!((schedule.next_temp(9.5, 5) - 7.0).abs() < 1e-12);
+    /// assert_eq!(schedule.next_temp(1.0, 100), 1.0);
+    /// ```
+    pub fn with_step(initial_temperature: f64, step: f64, min_temperature: f64) -> Self {
+        assert!(step > 0.0, "Step must be positive");
+        validate_budget(initial_temperature, min_temperature, 1);
+        Self {
+            initial_temperature,
+            final_temperature: min_temperature,
+            iterations: (initial_temperature - min_temperature) / step,
+        }
+    }
+}
+
+budget_schedule!(
+    /// An exponential cooling schedule that reaches a final temperature at the end of its budget.
+    ///
+    /// The temperature at iteration k of a budget of n iterations is given by:
+    /// T(k) = T_start * (T_end / T_start)^(k / n)
+    ///
+    /// Unlike `GeometricSchedule`, the temperature is computed from the
+    /// iteration number rather than the current temperature, so the decay
+    /// replays from the start after a reheat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::schedule::ExponentialSchedule;
+    /// use frostfire::prelude::*;
+    ///
+    /// let schedule = ExponentialSchedule::new(100.0, 1.0, 1000);
+    /// assert!((schedule.next_temp(100.0, 499) - 10.0).abs() < 1e-9);
+    /// assert!((schedule.next_temp(10.0, 999) - 1.0).abs() < 1e-9);
+    /// ```
+    ExponentialSchedule,
+    Decay::Exponential
+);
+
+budget_schedule!(
+    /// A cosine cooling schedule that reaches a final temperature at the end of its budget.
+    ///
+    /// The temperature at iteration k of a budget of n iterations is given by:
+    /// T(k) = T_end + (T_start - T_end) * (1 + cos(pi * k / n)) / 2
+    ///
+    /// The temperature decreases slowly at first, fastest in the middle of the
+    /// budget, and slowly again near the end. Combined with
+    /// `ReheatPolicy::warm_restarts`, it gives cosine annealing with warm restarts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::schedule::CosineSchedule;
+    /// use frostfire::prelude::*;
+    ///
+    /// let schedule = CosineSchedule::new(100.0, 10.0, 1000);
+    /// assert!((schedule.next_temp(100.0, 499) - 55.0).abs() < 1e-9);
+    /// assert!((schedule.next_temp(55.0, 999) - 10.0).abs() < 1e-9);
+    /// ```
+    CosineSchedule,
+    Decay::Cosine
+);
+
+/// A cooling schedule driven by the elapsed fraction of a wall-clock budget.
+///
+/// The temperature follows a `Decay` shape from T_start to T_end over the
+/// time budget, regardless of how many iterations are performed, and holds at
+/// T_end once the budget is exhausted. Combine it with a `TimeLimit`
+/// termination criterion to stop the run when the budget is spent.
+///
+/// The clock starts at the first call to `next_temp`, so a schedule should
+/// drive a single run; clone it before the run to reuse it.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::schedule::{Decay, WallClockSchedule};
+/// use frostfire::core::termination::TimeLimit;
+/// use frostfire::prelude::*;
+/// use std::time::Duration;
+///
+/// # #[derive(Clone)]
+/// # struct MyState;
+/// # impl State for MyState {
+/// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+/// # }
+/// let budget = Duration::from_millis(50);
+/// let mut annealer = Annealer::new(
+///     MyState,
+///     MyEnergy,
+///     WallClockSchedule::new(100.0, 0.1, budget, Decay::Exponential),
+///     seeded_rng(42),
+///     usize::MAX,
+/// )
+/// .with_termination(TimeLimit::new(budget));
+///
+/// let result = annealer.run_with_stats();
+/// assert!(result.final_temp < 1.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WallClockSchedule {
+    initial_temperature: f64,
+    final_temperature: f64,
+    budget: Duration,
+    decay: Decay,
+    started: OnceLock<Instant>,
+}
+
+impl WallClockSchedule {
+    /// Creates a new schedule decaying over a wall-clock budget.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    /// * `final_temperature`: The temperature once the budget is spent (positive, below the initial temperature)
+    /// * `budget`: The wall-clock duration of the decay (must be positive)
+    /// * `decay`: The shape of the decay
+    ///
+    /// # Panics
+    ///
+    /// Panics if the temperatures are not positive and decreasing, or if `budget` is zero.
+    pub fn new(
+        initial_temperature: f64,
+        final_temperature: f64,
+        budget: Duration,
+        decay: Decay,
+    ) -> Self {
+        validate_budget(initial_temperature, final_temperature, 1);
+        assert!(!budget.is_zero(), "Time budget must be positive");
+        Self {
+            initial_temperature,
+            final_temperature,
+            budget,
+            decay,
+            started: OnceLock::new(),
+        }
+    }
+
+    /// Returns the time elapsed since the clock started.
+    fn elapsed(&self) -> Duration {
+        self.started.get_or_init(Instant::now).elapsed()
+    }
+}
+
+impl Schedule for WallClockSchedule {
+    fn initial_temp(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    fn next_temp(&self, _current_temp: f64, _iteration: usize) -> f64 {
+        let fraction = self.elapsed().as_secs_f64() / self.budget.as_secs_f64();
+        self.decay
+            .temperature(self.initial_temperature, self.final_temperature, fraction)
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.elapsed().as_secs_f64().write_to(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        // Shift the start so the resumed run continues at the same fraction
+        let elapsed = Duration::from_secs_f64(f64::read_from(reader)?);
+        let now = Instant::now();
+        self.started = OnceLock::from(now.checked_sub(elapsed).unwrap_or(now));
+        Ok(())
+    }
+}
+
+/// The Lundy–Mees cooling schedule.
+///
+/// Designed so that a single iteration is performed at each temperature,
+/// the temperature decreases according to:
+/// T(k + 1) = T(k) / (1 + beta * T(k))
+///
+/// which gives the closed form T(k) = T(0) / (1 + k * beta * T(0)).
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::schedule::LundyMeesSchedule;
+/// use frostfire::prelude::*;
+///
+/// let schedule = LundyMeesSchedule::new(10.0, 0.01);
+/// assert!((schedule.next_temp(10.0, 0) - 10.0 / 1.1).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LundyMeesSchedule {
+    initial_temperature: f64,
+    beta: f64,
+}
+
+impl LundyMeesSchedule {
+    /// Creates a new Lundy–Mees schedule.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    /// * `beta`: The cooling parameter (must be positive); smaller values cool more slowly
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_temperature` or `beta` is not positive.
+    pub fn new(initial_temperature: f64, beta: f64) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "Initial temperature must be positive"
+        );
+        assert!(beta > 0.0, "Beta must be positive");
+        Self {
+            initial_temperature,
+            beta,
+        }
+    }
+}
+
+impl Schedule for LundyMeesSchedule {
+    fn initial_temp(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    fn next_temp(&self, current_temp: f64, _iteration: usize) -> f64 {
+        current_temp / (1.0 + self.beta * current_temp)
+    }
+}
+
+/// The Cauchy (fast annealing) cooling schedule of Szu and Hartley.
+///
+/// The temperature used at iteration k is given by:
+/// T(k) = T(0) / (1 + k)
+///
+/// It cools much faster than the logarithmic schedule and is commonly paired
+/// with heavy-tailed (Cauchy) neighbor distributions.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::schedule::CauchySchedule;
+/// use frostfire::prelude::*;
+///
+/// let schedule = CauchySchedule::new(10.0);
+/// // The temperature for iteration 4
+/// assert_eq!(schedule.next_temp(10.0, 3), 2.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CauchySchedule {
+    initial_temperature: f64,
+}
+
+impl CauchySchedule {
+    /// Creates a new Cauchy schedule.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_temperature` is not positive.
+    pub fn new(initial_temperature: f64) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "Initial temperature must be positive"
+        );
+        Self {
+            initial_temperature,
+        }
+    }
+}
+
+impl Schedule for CauchySchedule {
+    fn initial_temp(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    fn next_temp(&self, _current_temp: f64, iteration: usize) -> f64 {
+        self.initial_temperature / (2.0 + iteration as f64)
+    }
+}
+
+/// The quadratic multiplicative cooling schedule.
+///
+/// The temperature used at iteration k is given by:
+/// T(k) = T(0) / (1 + alpha * k^2)
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::schedule::QuadraticSchedule;
+/// use frostfire::prelude::*;
+///
+/// let schedule = QuadraticSchedule::new(10.0, 0.25);
+/// // The temperature for iteration 2
+/// assert_eq!(schedule.next_temp(10.0, 1), 5.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct QuadraticSchedule {
+    initial_temperature: f64,
+    alpha: f64,
+}
+
+impl QuadraticSchedule {
+    /// Creates a new quadratic multiplicative schedule.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    /// * `alpha`: The cooling parameter (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_temperature` or `alpha` is not positive.
+    pub fn new(initial_temperature: f64, alpha: f64) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "Initial temperature must be positive"
+        );
+        assert!(alpha > 0.0, "Alpha must be positive");
+        Self {
+            initial_temperature,
+            alpha,
+        }
+    }
+}
+
+impl Schedule for QuadraticSchedule {
+    fn initial_temp(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    fn next_temp(&self, _current_temp: f64, iteration: usize) -> f64 {
+        let k = (iteration + 1) as f64;
+        self.initial_temperature / (1.0 + self.alpha * k * k)
+    }
+}
+
+/// The energy-variance based schedule of Huang, Romeo and Sangiovanni-Vincentelli.
+///
+/// The temperature decreases according to:
+/// T(k + 1) = T(k) * max(0.5, exp(-lambda * T(k) / sigma))
+///
+/// where sigma is the standard deviation of the current energy over a window
+/// of recent iterations. While the energy fluctuates strongly compared to the
+/// temperature, cooling is slow; once the fluctuations die down, the
+/// temperature drops faster, but never by more than half per iteration.
+///
+/// Until the window has filled, the temperature is held while no fluctuation
+/// has been measured, so a run does not lose its initial temperature before
+/// sigma is known. A full window without any fluctuation halves it.
+///
+/// The energies are reported through `Schedule::observe`, so the schedule
+/// must be driven by an `Annealer` (or fed feedback manually).
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::schedule::HuangSchedule;
+/// use frostfire::prelude::*;
+///
+/// let schedule = HuangSchedule::new(100.0, 0.7);
+/// // Before any fluctuation is measured the temperature is held
+/// assert_eq!(schedule.next_temp(100.0, 0), 100.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HuangSchedule {
+    initial_temperature: f64,
+    lambda: f64,
+    energies: VecDeque<f64>,
+    window_size: usize,
+}
+
+impl HuangSchedule {
+    /// Creates a new Huang schedule with a window of 100 iterations.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    /// * `lambda`: The cooling parameter (must be positive); 0.7 is the classic choice
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_temperature` or `lambda` is not positive.
+    pub fn new(initial_temperature: f64, lambda: f64) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "Initial temperature must be positive"
+        );
+        assert!(lambda > 0.0, "Lambda must be positive");
+        Self {
+            initial_temperature,
+            lambda,
+            energies: VecDeque::new(),
+            window_size: 100,
+        }
+    }
+
+    /// Sets the number of recent iterations over which the energy deviation is measured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is less than 2.
+    pub fn with_window(mut self, window_size: usize) -> Self {
+        assert!(window_size >= 2, "Window size must be at least 2");
+        self.window_size = window_size;
+        self
+    }
+
+    /// Computes the standard deviation of the energies in the window.
+    fn energy_std_dev(&self) -> f64 {
+        if self.energies.len() < 2 {
+            return 0.0;
+        }
+        let n = self.energies.len() as f64;
+        let mean = self.energies.iter().sum::<f64>() / n;
+        let variance = self
+            .energies
+            .iter()
+            .map(|e| (e - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        variance.sqrt()
+    }
+}
+
+impl Schedule for HuangSchedule {
+    fn initial_temp(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    fn next_temp(&self, current_temp: f64, _iteration: usize) -> f64 {
+        let sigma = self.energy_std_dev();
+        let factor = if sigma > 0.0 {
+            (-self.lambda * current_temp / sigma).exp().max(0.5)
+        } else if self.energies.len() < self.window_size {
+            // Sigma is not known yet
+            1.0
+        } else {
+            0.5
+        };
+        current_temp * factor
+    }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        self.energies.push_back(feedback.current_energy);
+        if self.energies.len() > self.window_size {
+            self.energies.pop_front();
+        }
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        let energies: Vec<f64> = self.energies.iter().copied().collect();
+        energies.write_to(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.energies = Vec::<f64>::read_from(reader)?.into();
+        Ok(())
+    }
+}
+
+/// The modified Lam–Delosme schedule, which steers the acceptance rate along a target curve.
+///
+/// Lam and Delosme derived an optimal schedule keeping the search in
+/// quasi-equilibrium; this is the widely used simplification by Swartz and
+/// Boyan. Over an iteration budget of n iterations the target acceptance rate
+///
+/// - falls exponentially from 1 to 0.44 during the first 15% of the budget,
+/// - stays at 0.44 until 65% of the budget,
+/// - falls exponentially towards 0 during the remaining 35%.
+///
+/// After every iteration the temperature is multiplied by 0.999 if the
+/// measured acceptance rate (an exponential moving average over roughly 500
+/// iterations) is above the target, and divided by 0.999 otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::schedule::LamDelosmeSchedule;
+/// use frostfire::prelude::*;
+///
+/// let schedule = LamDelosmeSchedule::new(10.0, 100_000);
+/// assert!((schedule.target_acceptance(0) - 1.0).abs() < 1e-12);
+/// assert_eq!(schedule.target_acceptance(50_000), 0.44);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LamDelosmeSchedule {
+    initial_temperature: f64,
+    iterations: usize,
+    acceptance_rate: f64,
+}
+
+impl LamDelosmeSchedule {
+    /// Creates a new modified Lam–Delosme schedule.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_temperature`: The starting temperature (must be positive)
+    /// * `iterations`: The iteration budget over which the target curve is laid out (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_temperature` or `iterations` is not positive.
+    pub fn new(initial_temperature: f64, iterations: usize) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "Initial temperature must be positive"
+        );
+        assert!(iterations > 0, "Iteration budget must be positive");
+        Self {
+            initial_temperature,
+            iterations,
+            acceptance_rate: 0.5,
+        }
+    }
+
+    /// Returns the target acceptance rate at an iteration.
+    ///
+    /// # Parameters
+    ///
+    /// * `iteration`: The iteration number (0-based)
+    pub fn target_acceptance(&self, iteration: usize) -> f64 {
+        let fraction = iteration as f64 / self.iterations as f64;
+        if fraction < 0.15 {
+            0.44 + 0.56 * 560.0_f64.powf(-fraction / 0.15)
+        } else if fraction < 0.65 {
+            0.44
+        } else {
+            0.44 * 440.0_f64.powf(-(fraction - 0.65) / 0.35)
+        }
+    }
+
+    /// Returns the measured acceptance rate.
+    pub fn acceptance_rate(&self) -> f64 {
+        self.acceptance_rate
+    }
+}
+
+impl Schedule for LamDelosmeSchedule {
+    fn initial_temp(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    fn next_temp(&self, current_temp: f64, iteration: usize) -> f64 {
+        if self.acceptance_rate > self.target_acceptance(iteration) {
+            current_temp * 0.999
+        } else {
+            current_temp / 0.999
+        }
+    }
+
+    fn observe(&mut self, feedback: &Feedback) {
+        let accepted = if feedback.accepted { 1.0 } else { 0.0 };
+        self.acceptance_rate = (499.0 * self.acceptance_rate + accepted) / 500.0;
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.acceptance_rate.write_to(writer)
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        self.acceptance_rate = f64::read_from(reader)?;
+        Ok(())
+    }
+}
+
+/// How a `TabulatedSchedule` interpolates between its control points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Temperatures change linearly between control points
+    Linear,
+    /// Temperatures change geometrically between control points, so that
+    /// the logarithm of the temperature is linear in the iteration
+    Log,
+}
+
+/// A schedule replaying a temperature profile given as control points.
+///
+/// The profile is a list of (iteration, temperature) pairs with strictly
+/// increasing iterations, for example derived offline from past runs.
+/// Between control points the temperature is interpolated; before the first
+/// point the first temperature is used and after the last point the last
+/// temperature is held.
+///
+/// Profiles can be shipped as data and loaded with `from_csv`.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::schedule::{Interpolation, TabulatedSchedule};
+/// use frostfire::prelude::*;
+///
+/// let schedule = TabulatedSchedule::new(
+///     vec![(0, 100.0), (1000, 1.0), (2000, 0.1)],
+///     Interpolation::Log,
+/// );
+///
+/// assert_eq!(schedule.initial_temp(), 100.0);
+/// assert!((schedule.temperature_at(500) - 10.0).abs() < 1e-9);
+/// assert_eq!(schedule.temperature_at(5000), 0.1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TabulatedSchedule {
+    points: Vec<(usize, f64)>,
+    interpolation: Interpolation,
+}
+
+impl TabulatedSchedule {
+    /// Creates a new schedule from control points.
+    ///
+    /// # Parameters
+    ///
+    /// * `points`: The (iteration, temperature) control points, with strictly increasing iterations
+    /// * `interpolation`: How temperatures between control points are computed
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is empty, the iterations are not strictly increasing,
+    /// or a temperature is not positive.
+    pub fn new(points: Vec<(usize, f64)>, interpolation: Interpolation) -> Self {
+        if let Err(message) = Self::validate(&points) {
+            panic!("{}", message);
+        }
+        Self {
+            points,
+            interpolation,
+        }
+    }
+
+    /// Loads control points from a CSV file.
+    ///
+    /// See `from_reader` for the format.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: The CSV file to read
+    /// * `interpolation`: How temperatures between control points are computed
+    ///
+    /// # Returns
+    ///
+    /// The schedule, or an error if the file cannot be read or is not a valid profile.
+    pub fn from_csv(path: impl AsRef<Path>, interpolation: Interpolation) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?), interpolation)
+    }
+
+    /// Reads control points in CSV format.
+    ///
+    /// Each line holds an iteration and a temperature separated by a comma.
+    /// Blank lines and lines starting with `#` are ignored, and the first
+    /// remaining line may be a header such as `iteration,temperature`. A first
+    /// line starting with a number is always read as a control point.
+    ///
+    /// # Parameters
+    ///
+    /// * `reader`: The CSV data
+    /// * `interpolation`: How temperatures between control points are computed
+    ///
+    /// # Returns
+    ///
+    /// The schedule, or an error of kind `InvalidData` if the data is not a valid profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use frostfire::core::schedule::{Interpolation, TabulatedSchedule};
+    ///
+    /// let csv = "iteration,temperature\n0,10.0\n100,2.5\n";
+    /// let schedule = TabulatedSchedule::from_reader(csv.as_bytes(), Interpolation::Linear)
+    ///     .expect("invalid profile");
+    /// assert_eq!(schedule.temperature_at(50), 6.25);
+    /// ```
+    pub fn from_reader(reader: impl BufRead, interpolation: Interpolation) -> io::Result<Self> {
+        let mut points = Vec::new();
+        let mut first_record = true;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parsed = line.split_once(',').and_then(|(iteration, temperature)| {
+                let iteration = iteration.trim().parse::<usize>().ok()?;
+                let temperature = temperature.trim().parse::<f64>().ok()?;
+                Some((iteration, temperature))
+            });
+            // Only the first record may be a header, and only if it does not
+            // start with a number
+            let header = first_record
+                && !line.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'));
+            match parsed {
+                Some(point) => points.push(point),
+                None if header => {}
+                None => {
+                    return Err(invalid_data(&format!(
+                        "line {}: expected `iteration,temperature`, found `{}`",
+                        index + 1,
+                        line
+                    )))
+                }
+            }
+            first_record = false;
+        }
+
+        Self::validate(&points).map_err(|message| invalid_data(&message))?;
+        Ok(Self {
+            points,
+            interpolation,
+        })
+    }
+
+    /// Checks that the control points describe a valid profile.
+    fn validate(points: &[(usize, f64)]) -> Result<(), String> {
+        if points.is_empty() {
+            return Err("Temperature profile must have at least one control point".to_string());
+        }
+        if let Some(&(iteration, temperature)) = points
+            .iter()
+            .find(|(_, temperature)| !(*temperature > 0.0 && temperature.is_finite()))
+        {
+            return Err(format!(
+                "Temperature at iteration {} must be positive, found {}",
+                iteration, temperature
+            ));
+        }
+        if let Some(pair) = points.windows(2).find(|pair| pair[1].0 <= pair[0].0) {
+            return Err(format!(
+                "Control point iterations must be strictly increasing, found {} after {}",
+                pair[1].0, pair[0].0
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the control points of the profile.
+    pub fn points(&self) -> &[(usize, f64)] {
+        &self.points
+    }
+
+    /// Returns the temperature of the profile at an iteration.
+    ///
+    /// # Parameters
+    ///
+    /// * `iteration`: The iteration number (0-based)
+    pub fn temperature_at(&self, iteration: usize) -> f64 {
+        // The index of the first control point after the iteration
+        let next = self.points.partition_point(|&(at, _)| at <= iteration);
+        if next == 0 {
+            return self.points[0].1;
+        }
+        if next == self.points.len() {
+            return self.points[next - 1].1;
+        }
+
+        let (start, start_temp) = self.points[next - 1];
+        let (end, end_temp) = self.points[next];
+        let fraction = (iteration - start) as f64 / (end - start) as f64;
+        match self.interpolation {
+            Interpolation::Linear => start_temp + (end_temp - start_temp) * fraction,
+            Interpolation::Log => start_temp * (end_temp / start_temp).powf(fraction),
+        }
+    }
+}
+
+impl Schedule for TabulatedSchedule {
+    fn initial_temp(&self) -> f64 {
+        self.temperature_at(0)
+    }
+
+    fn next_temp(&self, _current_temp: f64, iteration: usize) -> f64 {
+        self.temperature_at(iteration + 1)
+    }
 }
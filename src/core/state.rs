@@ -2,6 +2,9 @@
 //!
 //! The `State` trait represents a candidate solution in the search space.
 //! It provides methods for generating neighboring states during the annealing process.
+//!
+//! States that are expensive to clone can instead implement `MoveState`, which
+//! describes neighbors as lightweight moves applied to the state in place.
 
 use rand::Rng;
 
@@ -45,3 +48,104 @@ pub trait State: Clone + Send + Sync {
     /// A new state that is a neighbor of the current state.
     fn neighbor(&self, rng: &mut impl Rng) -> Self;
 }
+
+/// The `MoveState` trait describes a candidate solution whose neighbors are
+/// reached by applying a lightweight move in place.
+///
+/// This is the trait the `Annealer` drives. Each iteration it proposes a move,
+/// evaluates it through `Energy::delta`, and only applies it when accepted, so
+/// states holding large vectors or graphs are never cloned per iteration. A copy
+/// is made only when a new best state is recorded.
+///
+/// Every `State` is a `MoveState` whose move is the full neighboring state, so
+/// existing `State` implementations work unchanged. Implement `MoveState`
+/// directly (instead of `State`) to opt into in-place moves.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Permutation(Vec<usize>);
+///
+/// impl MoveState for Permutation {
+///     // Swap the elements at two positions
+///     type Move = (usize, usize);
+///
+///     fn propose_move(&self, rng: &mut impl Rng) -> Self::Move {
+///         (rng.gen_range(0..self.0.len()), rng.gen_range(0..self.0.len()))
+///     }
+///
+///     fn apply_move(&mut self, mv: &mut Self::Move) {
+///         self.0.swap(mv.0, mv.1);
+///     }
+///
+///     fn undo_move(&mut self, mv: &mut Self::Move) {
+///         // A swap is its own inverse
+///         self.0.swap(mv.0, mv.1);
+///     }
+/// }
+///
+/// let mut state = Permutation(vec![0, 1, 2, 3]);
+/// let mut mv = state.propose_move(&mut seeded_rng(42));
+/// state.apply_move(&mut mv);
+/// state.undo_move(&mut mv);
+/// assert_eq!(state.0, vec![0, 1, 2, 3]);
+/// ```
+pub trait MoveState: Clone + Send + Sync {
+    /// The description of a modification to the state.
+    type Move;
+
+    /// Proposes a random move from the current state without applying it.
+    ///
+    /// # Parameters
+    ///
+    /// * `rng`: A random number generator used to choose the move.
+    ///
+    /// # Returns
+    ///
+    /// A move leading to a neighbor of the current state.
+    fn propose_move(&self, rng: &mut impl Rng) -> Self::Move;
+
+    /// Applies a move to the state in place.
+    ///
+    /// The move is passed mutably so implementations can stash whatever they
+    /// need to revert it later, such as overwritten values.
+    ///
+    /// # Parameters
+    ///
+    /// * `mv`: The move to apply, as returned by `propose_move`
+    fn apply_move(&mut self, mv: &mut Self::Move);
+
+    /// Reverts a move previously applied with `apply_move`.
+    ///
+    /// After `apply_move(mv)` followed by `undo_move(mv)` the state must be
+    /// identical to what it was before the move was applied.
+    ///
+    /// # Parameters
+    ///
+    /// * `mv`: The move to revert
+    fn undo_move(&mut self, mv: &mut Self::Move);
+}
+
+/// Neighbor-based states use the whole neighboring state as their move.
+///
+/// Applying and reverting such a move swaps the neighbor in and out, so no
+/// additional copies are made beyond the one created by `neighbor`.
+impl<S: State> MoveState for S {
+    type Move = S;
+
+    fn propose_move(&self, rng: &mut impl Rng) -> Self::Move {
+        self.neighbor(rng)
+    }
+
+    fn apply_move(&mut self, mv: &mut Self::Move) {
+        std::mem::swap(self, mv);
+    }
+
+    fn undo_move(&mut self, mv: &mut Self::Move) {
+        std::mem::swap(self, mv);
+    }
+}
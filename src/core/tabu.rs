@@ -0,0 +1,258 @@
+//! Tabu memory for simulated annealing.
+//!
+//! On some problems, such as assignment problems, simulated annealing keeps
+//! revisiting the same few configurations. A `TabuList` remembers attributes
+//! of recently accepted moves (or recently visited states) and makes the
+//! `Annealer` reject proposals sharing those attributes for a number of
+//! iterations, pushing the search towards unexplored configurations.
+
+use crate::core::checkpoint::Persist;
+use crate::core::state::MoveState;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{self, Read, Write};
+
+/// Extracts an attribute from the current state and a proposed move.
+type MoveAttribute<S, T> = Box<dyn Fn(&S, &<S as MoveState>::Move) -> T + Send>;
+
+/// Extracts the attribute remembered by a `TabuList`.
+enum Attribute<S: MoveState, T> {
+    /// From the current state and the proposed move
+    Move(MoveAttribute<S, T>),
+    /// From the state the proposed move leads to
+    State(Box<dyn Fn(&S) -> T + Send>),
+}
+
+/// A short-term memory of recent moves that the annealer may not repeat.
+///
+/// When a move is accepted, its attribute becomes tabu for `tenure`
+/// iterations. While an attribute is tabu, proposals with the same attribute
+/// are rejected without consulting the acceptance rule, unless aspiration is
+/// enabled and the proposal would yield a new best energy. Proposals rejected
+/// as tabu are counted in `AnnealingResult::tabu_rejections` (and in
+/// `rejected_moves`).
+///
+/// The attribute is what makes two moves "the same" for the purposes of the
+/// memory. Choosing it is problem specific: for an assignment problem, the
+/// item being reassigned forbids moving it again for a while, and a
+/// normalized pair of positions forbids undoing a swap.
+///
+/// The tabu memory is part of checkpoints, so attributes must implement
+/// `Persist`; a resumed run keeps rejecting the moves that were tabu when the
+/// checkpoint was written.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::tabu::TabuList;
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Assignment(Vec<usize>);
+///
+/// impl MoveState for Assignment {
+///     // Swap the assignments of two items
+///     type Move = (usize, usize);
+///
+///     fn propose_move(&self, rng: &mut impl Rng) -> Self::Move {
+///         (rng.gen_range(0..self.0.len()), rng.gen_range(0..self.0.len()))
+///     }
+///
+///     fn apply_move(&mut self, mv: &mut Self::Move) {
+///         self.0.swap(mv.0, mv.1);
+///     }
+///
+///     fn undo_move(&mut self, mv: &mut Self::Move) {
+///         self.0.swap(mv.0, mv.1);
+///     }
+/// }
+///
+/// struct Cost;
+///
+/// impl Energy for Cost {
+///     type State = Assignment;
+///
+///     fn cost(&self, state: &Assignment) -> f64 {
+///         state.0.iter().enumerate().map(|(i, &slot)| (i * slot) as f64).sum()
+///     }
+/// }
+///
+/// // A swap may not be undone for 20 iterations, unless it finds a new best
+/// let tabu = TabuList::on_moves(20, |_: &Assignment, &(a, b): &(usize, usize)| {
+///     (a.min(b), a.max(b))
+/// });
+///
+/// let mut annealer = Annealer::new(
+///     Assignment((0..10).collect()),
+///     Cost,
+///     GeometricSchedule::new(10.0, 0.999),
+///     seeded_rng(42),
+///     10000,
+/// )
+/// .with_tabu(tabu);
+///
+/// let result = annealer.run_with_stats();
+/// println!("{} proposals were tabu", result.tabu_rejections);
+/// ```
+pub struct TabuList<S: MoveState, T> {
+    attribute: Attribute<S, T>,
+    tenure: usize,
+    aspiration: bool,
+    /// The iteration at which each tabu attribute is released
+    expiry: HashMap<T, usize>,
+    /// The attribute of the proposal being evaluated
+    pending: Option<T>,
+}
+
+impl<S: MoveState, T: Eq + Hash + Send> TabuList<S, T> {
+    /// Creates a tabu list remembering an attribute of each accepted move.
+    ///
+    /// For `State` types the move is the neighboring state itself, so the
+    /// extractor receives both the current and the proposed state.
+    ///
+    /// # Parameters
+    ///
+    /// * `tenure`: The number of iterations an attribute stays tabu (must be positive)
+    /// * `attribute`: Extracts the attribute from the current state and the proposed move
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tenure` is zero.
+    pub fn on_moves(tenure: usize, attribute: impl Fn(&S, &S::Move) -> T + Send + 'static) -> Self {
+        Self::with_attribute(tenure, Attribute::Move(Box::new(attribute)))
+    }
+
+    /// Creates a tabu list remembering an attribute of each visited state.
+    ///
+    /// Proposals leading to a state whose attribute was visited recently are
+    /// tabu. To compute the attribute, each proposed move is applied and
+    /// reverted once more, which costs an extra pair of `apply_move` and
+    /// `undo_move` calls per iteration.
+    ///
+    /// # Parameters
+    ///
+    /// * `tenure`: The number of iterations an attribute stays tabu (must be positive)
+    /// * `attribute`: Extracts the attribute from a state
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tenure` is zero.
+    pub fn on_states(tenure: usize, attribute: impl Fn(&S) -> T + Send + 'static) -> Self {
+        Self::with_attribute(tenure, Attribute::State(Box::new(attribute)))
+    }
+
+    fn with_attribute(tenure: usize, attribute: Attribute<S, T>) -> Self {
+        assert!(tenure > 0, "Tabu tenure must be positive");
+        Self {
+            attribute,
+            tenure,
+            aspiration: true,
+            expiry: HashMap::new(),
+            pending: None,
+        }
+    }
+
+    /// Enables or disables aspiration.
+    ///
+    /// With aspiration (the default), a tabu proposal is still considered if
+    /// it would yield a new best energy.
+    pub fn with_aspiration(mut self, aspiration: bool) -> Self {
+        self.aspiration = aspiration;
+        self
+    }
+
+    /// Returns the number of iterations an attribute stays tabu.
+    pub fn tenure(&self) -> usize {
+        self.tenure
+    }
+}
+
+/// The tabu memory as driven by the annealer, independent of the attribute type.
+pub(crate) trait TabuMemory<S: MoveState>: Send {
+    /// Forgets all tabu attributes.
+    fn clear(&mut self);
+
+    /// Returns whether a proposal is tabu at an iteration, and remembers its
+    /// attribute so it can be recorded if the proposal is accepted.
+    fn check(&mut self, state: &mut S, mv: &mut S::Move, iteration: usize) -> bool;
+
+    /// Makes the attribute of the last checked proposal tabu.
+    fn record(&mut self, iteration: usize);
+
+    /// Returns whether tabu proposals yielding a new best are considered.
+    fn aspiration(&self) -> bool;
+
+    /// Writes the tabu attributes into a checkpoint.
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Restores the tabu attributes written by `save_state`.
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()>;
+}
+
+impl<S: MoveState, T: Eq + Hash + Send + Persist> TabuMemory<S> for TabuList<S, T> {
+    fn clear(&mut self) {
+        self.expiry.clear();
+        self.pending = None;
+    }
+
+    fn check(&mut self, state: &mut S, mv: &mut S::Move, iteration: usize) -> bool {
+        let attribute = match &self.attribute {
+            Attribute::Move(attribute) => attribute(state, mv),
+            Attribute::State(attribute) => {
+                state.apply_move(mv);
+                let value = attribute(state);
+                state.undo_move(mv);
+                value
+            }
+        };
+        let tabu = self
+            .expiry
+            .get(&attribute)
+            .is_some_and(|&expires| iteration < expires);
+        self.pending = Some(attribute);
+        tabu
+    }
+
+    fn record(&mut self, iteration: usize) {
+        // Drop released attributes now and then so the memory stays small
+        if iteration.is_multiple_of(self.tenure) {
+            self.expiry.retain(|_, &mut expires| iteration < expires);
+        }
+        if let Some(attribute) = self.pending.take() {
+            self.expiry.insert(attribute, iteration + 1 + self.tenure);
+        }
+    }
+
+    fn aspiration(&self) -> bool {
+        self.aspiration
+    }
+
+    fn save_state(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.expiry.len().write_to(writer)?;
+        for (attribute, expires) in &self.expiry {
+            attribute.write_to(writer)?;
+            expires.write_to(writer)?;
+        }
+        self.pending.is_some().write_to(writer)?;
+        match &self.pending {
+            Some(attribute) => attribute.write_to(writer),
+            None => Ok(()),
+        }
+    }
+
+    fn load_state(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        let len = usize::read_from(reader)?;
+        let mut expiry = HashMap::new();
+        for _ in 0..len {
+            let attribute = T::read_from(reader)?;
+            expiry.insert(attribute, usize::read_from(reader)?);
+        }
+        self.expiry = expiry;
+        self.pending = match bool::read_from(reader)? {
+            true => Some(T::read_from(reader)?),
+            false => None,
+        };
+        Ok(())
+    }
+}
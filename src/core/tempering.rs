@@ -0,0 +1,351 @@
+//! Parallel tempering (replica exchange) engine.
+//!
+//! Parallel tempering runs several replicas of a state at a ladder of fixed
+//! temperatures. Hot replicas explore freely while cold replicas refine, and
+//! periodic Metropolis swaps between neighboring temperatures let good
+//! configurations found at high temperature migrate down the ladder.
+
+use crate::core::energy::Energy;
+use crate::core::state::MoveState;
+use crate::core::transition;
+use crate::rng::seeded_rng::derived_rng;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt;
+use std::thread;
+
+/// Results from a parallel tempering run.
+#[derive(Clone)]
+pub struct TemperingResult<S: MoveState> {
+    /// The best state found by any replica
+    pub best_state: S,
+    /// The energy (cost) of the best state
+    pub best_energy: f64,
+    /// The temperature ladder, in ascending order
+    pub temperatures: Vec<f64>,
+    /// The final state at each temperature
+    pub states: Vec<S>,
+    /// The energy of the final state at each temperature
+    pub energies: Vec<f64>,
+    /// The fraction of accepted Metropolis moves at each temperature
+    pub acceptance_rates: Vec<f64>,
+    /// The number of swaps attempted between temperatures `k` and `k + 1`
+    pub swap_attempts: Vec<usize>,
+    /// The fraction of accepted swaps between temperatures `k` and `k + 1`
+    pub swap_acceptance_rates: Vec<f64>,
+    /// The number of sweeps performed
+    pub sweeps: usize,
+}
+
+impl<S: MoveState> fmt::Debug for TemperingResult<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemperingResult")
+            .field("best_energy", &self.best_energy)
+            .field("temperatures", &self.temperatures)
+            .field("energies", &self.energies)
+            .field("acceptance_rates", &self.acceptance_rates)
+            .field("swap_acceptance_rates", &self.swap_acceptance_rates)
+            .field("sweeps", &self.sweeps)
+            .finish()
+    }
+}
+
+/// A replica bound to one temperature of the ladder.
+///
+/// States migrate between replicas through swaps, but each replica keeps its
+/// own random number generator, which makes the run independent of how
+/// replicas are distributed across threads.
+struct Replica<S> {
+    state: S,
+    energy: f64,
+    best_state: S,
+    best_energy: f64,
+    rng: StdRng,
+    accepted: usize,
+}
+
+impl<S: MoveState> Replica<S> {
+    /// Runs a number of Metropolis steps at the replica's temperature.
+    fn advance<E: Energy<State = S>>(&mut self, energy: &E, temperature: f64, steps: usize) {
+        for _ in 0..steps {
+            if transition::metropolis_step(
+                energy,
+                &mut self.state,
+                &mut self.energy,
+                temperature,
+                &mut self.rng,
+            ) {
+                self.accepted += 1;
+                if self.energy < self.best_energy {
+                    self.best_state.clone_from(&self.state);
+                    self.best_energy = self.energy;
+                }
+            }
+        }
+    }
+}
+
+/// Parallel tempering engine running replicas at a ladder of fixed temperatures.
+///
+/// Each sweep runs `steps_per_sweep` Metropolis steps on every replica, in
+/// parallel across threads, and then attempts swaps between neighboring
+/// temperatures, alternating between even and odd pairs on successive sweeps.
+/// A swap between temperatures `T_k < T_{k+1}` is accepted with probability
+///
+/// P(swap) = min(1, exp((1/T_k - 1/T_{k+1}) * (E_k - E_{k+1})))
+///
+/// Every replica and the swap phase draw from their own streams derived from
+/// the seed with `derived_rng`, so results are identical for a given seed
+/// regardless of the number of threads.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::tempering::ParallelTempering;
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// #[derive(Clone)]
+/// struct Point(f64);
+///
+/// impl State for Point {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         Point(self.0 + rng.gen_range(-0.5..0.5))
+///     }
+/// }
+///
+/// // A double well with its global minimum near x = -1
+/// struct DoubleWell;
+///
+/// impl Energy for DoubleWell {
+///     type State = Point;
+///
+///     fn cost(&self, state: &Point) -> f64 {
+///         (state.0 * state.0 - 1.0).powi(2) + 0.3 * state.0
+///     }
+/// }
+///
+/// let tempering = ParallelTempering::new(
+///     Point(1.0),
+///     DoubleWell,
+///     vec![0.05, 0.1, 0.2, 0.4, 0.8],
+///     42,
+///     200,
+/// )
+/// .with_threads(2);
+///
+/// let result = tempering.run();
+/// assert!(result.best_state.0 < 0.0);
+/// assert_eq!(result.swap_acceptance_rates.len(), 4);
+/// ```
+pub struct ParallelTempering<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S> + Sync,
+{
+    initial_state: S,
+    energy: E,
+    temperatures: Vec<f64>,
+    seed: u64,
+    sweeps: usize,
+    steps_per_sweep: usize,
+    threads: usize,
+}
+
+impl<S, E> ParallelTempering<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S> + Sync,
+{
+    /// Creates a new parallel tempering engine.
+    ///
+    /// Every replica starts from a copy of `initial_state`. By default each
+    /// sweep runs 100 Metropolis steps per replica, using as many threads as
+    /// the machine provides.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_state`: The starting state of every replica
+    /// * `energy`: The energy function to be minimized
+    /// * `temperatures`: The temperature ladder (positive and strictly increasing)
+    /// * `seed`: The base seed from which all random streams are derived
+    /// * `sweeps`: The number of sweeps to perform
+    ///
+    /// # Panics
+    ///
+    /// Panics if `temperatures` is empty, not positive, or not strictly increasing.
+    pub fn new(
+        initial_state: S,
+        energy: E,
+        temperatures: Vec<f64>,
+        seed: u64,
+        sweeps: usize,
+    ) -> Self {
+        assert!(
+            !temperatures.is_empty(),
+            "At least one temperature is required"
+        );
+        assert!(
+            temperatures.iter().all(|&t| t > 0.0),
+            "Temperatures must be positive"
+        );
+        assert!(
+            temperatures.windows(2).all(|pair| pair[0] < pair[1]),
+            "Temperatures must be strictly increasing"
+        );
+
+        let threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Self {
+            initial_state,
+            energy,
+            temperatures,
+            seed,
+            sweeps,
+            steps_per_sweep: 100,
+            threads,
+        }
+    }
+
+    /// Sets the number of Metropolis steps each replica runs between swap attempts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` is zero.
+    pub fn with_steps_per_sweep(mut self, steps: usize) -> Self {
+        assert!(steps > 0, "Steps per sweep must be positive");
+        self.steps_per_sweep = steps;
+        self
+    }
+
+    /// Sets the number of threads used to advance the replicas.
+    ///
+    /// The results do not depend on this setting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "Thread count must be positive");
+        self.threads = threads;
+        self
+    }
+
+    /// Runs parallel tempering for the configured number of sweeps.
+    ///
+    /// # Returns
+    ///
+    /// A `TemperingResult` with the best state found, the final replicas and
+    /// the swap statistics for each pair of neighboring temperatures.
+    pub fn run(&self) -> TemperingResult<S> {
+        let count = self.temperatures.len();
+        let initial_energy = self.energy.cost(&self.initial_state);
+
+        let mut replicas: Vec<Replica<S>> = (0..count)
+            .map(|k| Replica {
+                state: self.initial_state.clone(),
+                energy: initial_energy,
+                best_state: self.initial_state.clone(),
+                best_energy: initial_energy,
+                rng: derived_rng(self.seed, k as u64),
+                accepted: 0,
+            })
+            .collect();
+
+        // The swap phase gets the stream following the replicas' streams
+        let mut swap_rng = derived_rng(self.seed, count as u64);
+        let mut swap_attempts = vec![0; count.saturating_sub(1)];
+        let mut swap_accepts = vec![0; count.saturating_sub(1)];
+
+        for sweep in 0..self.sweeps {
+            self.advance_replicas(&mut replicas);
+
+            // Alternate between even and odd pairs
+            for k in (sweep % 2..count.saturating_sub(1)).step_by(2) {
+                swap_attempts[k] += 1;
+                if self.try_swap(&mut replicas, k, &mut swap_rng) {
+                    swap_accepts[k] += 1;
+                }
+            }
+        }
+
+        let best = replicas
+            .iter()
+            .min_by(|a, b| a.best_energy.total_cmp(&b.best_energy))
+            .expect("at least one replica");
+        let best_state = best.best_state.clone();
+        let best_energy = best.best_energy;
+
+        let steps = (self.sweeps * self.steps_per_sweep).max(1) as f64;
+        let swap_acceptance_rates = swap_accepts
+            .iter()
+            .zip(&swap_attempts)
+            .map(|(&accepts, &attempts)| {
+                if attempts == 0 {
+                    0.0
+                } else {
+                    accepts as f64 / attempts as f64
+                }
+            })
+            .collect();
+
+        TemperingResult {
+            best_state,
+            best_energy,
+            temperatures: self.temperatures.clone(),
+            acceptance_rates: replicas.iter().map(|r| r.accepted as f64 / steps).collect(),
+            energies: replicas.iter().map(|r| r.energy).collect(),
+            states: replicas.into_iter().map(|r| r.state).collect(),
+            swap_attempts,
+            swap_acceptance_rates,
+            sweeps: self.sweeps,
+        }
+    }
+
+    /// Advances every replica by one sweep, spreading them across threads.
+    fn advance_replicas(&self, replicas: &mut [Replica<S>]) {
+        let steps = self.steps_per_sweep;
+        let energy = &self.energy;
+
+        if self.threads == 1 || replicas.len() == 1 {
+            for (replica, &temperature) in replicas.iter_mut().zip(&self.temperatures) {
+                replica.advance(energy, temperature, steps);
+            }
+            return;
+        }
+
+        let chunk_size = replicas.len().div_ceil(self.threads);
+        thread::scope(|scope| {
+            for (chunk, temperatures) in replicas
+                .chunks_mut(chunk_size)
+                .zip(self.temperatures.chunks(chunk_size))
+            {
+                scope.spawn(move || {
+                    for (replica, &temperature) in chunk.iter_mut().zip(temperatures) {
+                        replica.advance(energy, temperature, steps);
+                    }
+                });
+            }
+        });
+    }
+
+    /// Attempts to exchange the states of replicas `k` and `k + 1`.
+    fn try_swap(&self, replicas: &mut [Replica<S>], k: usize, rng: &mut StdRng) -> bool {
+        let beta_cold = 1.0 / self.temperatures[k];
+        let beta_hot = 1.0 / self.temperatures[k + 1];
+
+        let (cold_half, hot_half) = replicas.split_at_mut(k + 1);
+        let cold = &mut cold_half[k];
+        let hot = &mut hot_half[0];
+
+        let log_ratio = (beta_cold - beta_hot) * (cold.energy - hot.energy);
+        let accepted = log_ratio >= 0.0 || rng.gen::<f64>() < log_ratio.exp();
+        if accepted {
+            std::mem::swap(&mut cold.state, &mut hot.state);
+            std::mem::swap(&mut cold.energy, &mut hot.energy);
+        }
+        accepted
+    }
+}
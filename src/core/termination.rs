@@ -0,0 +1,612 @@
+//! Termination criteria for simulated annealing.
+//!
+//! This module provides composable stopping conditions that end an annealing
+//! run before its maximum number of iterations is reached, along with the
+//! `StopReason` reported in the `AnnealingResult`.
+
+use crate::core::checkpoint::Persist;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The reason an annealing run stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StopReason {
+    /// The maximum number of iterations was reached
+    MaxIterations,
+    /// The best energy reached the target energy
+    TargetEnergy,
+    /// The best energy did not improve for too many iterations
+    Stagnation,
+    /// The temperature dropped below the floor
+    TemperatureFloor,
+    /// The wall-clock budget was exhausted
+    TimeLimit,
+    /// The energy evaluation budget was exhausted
+    EvaluationBudget,
+    /// The caller finished the run early with `Annealer::finish`
+    Interrupted,
+    /// The run was cancelled through a `CancellationToken`
+    Cancelled,
+    /// The search froze: too few moves were accepted for several temperature levels
+    Frozen,
+    /// Every criterion of an `AllOf` combinator was satisfied, with their reasons
+    All(Vec<StopReason>),
+}
+
+/// A snapshot of the annealing progress, passed to termination criteria.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// The number of completed iterations
+    pub iteration: usize,
+    /// The number of energy evaluations performed (including the initial state)
+    pub evaluations: usize,
+    /// The temperature for the next iteration
+    pub temperature: f64,
+    /// The energy of the current state
+    pub current_energy: f64,
+    /// The best energy found so far
+    pub best_energy: f64,
+    /// The number of iterations since the best energy last improved
+    pub iterations_since_improvement: usize,
+    /// The wall-clock time elapsed since the run started
+    pub elapsed: Duration,
+}
+
+/// The `Termination` trait defines a condition that stops an annealing run.
+///
+/// Criteria are registered with `Annealer::with_termination` and checked before
+/// the first iteration and after every iteration. The run stops as soon as any registered criterion returns
+/// a reason, and that reason is reported in `AnnealingResult::stop_reason`.
+/// The maximum number of iterations always applies as a hard limit.
+///
+/// Criteria can be combined with `or` and `and`, or with the `AnyOf` and
+/// `AllOf` combinators directly.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::{NoImprovement, TargetEnergy, Termination, TimeLimit};
+/// use std::time::Duration;
+///
+/// // Stop when the target is reached, or after stagnating for 5 seconds
+/// let criterion = TargetEnergy::new(0.0)
+///     .or(NoImprovement::new(10_000).and(TimeLimit::new(Duration::from_secs(5))));
+/// ```
+pub trait Termination: Send {
+    /// Checks whether the run should stop.
+    ///
+    /// # Parameters
+    ///
+    /// * `progress`: The current progress of the run
+    ///
+    /// # Returns
+    ///
+    /// `Some(reason)` if the run should stop, `None` otherwise.
+    fn check(&mut self, progress: &Progress) -> Option<StopReason>;
+
+    /// Combines this criterion with another, stopping when either is satisfied.
+    fn or<T: Termination + 'static>(self, other: T) -> AnyOf
+    where
+        Self: Sized + 'static,
+    {
+        AnyOf::new().with(self).with(other)
+    }
+
+    /// Combines this criterion with another, stopping when both are satisfied.
+    fn and<T: Termination + 'static>(self, other: T) -> AllOf
+    where
+        Self: Sized + 'static,
+    {
+        AllOf::new().with(self).with(other)
+    }
+}
+
+/// Stops once the best energy is at or below a target value.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::TargetEnergy;
+///
+/// // Stop as soon as a zero-cost solution is found
+/// let criterion = TargetEnergy::new(0.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TargetEnergy {
+    target: f64,
+}
+
+impl TargetEnergy {
+    /// Creates a new target energy criterion.
+    ///
+    /// # Parameters
+    ///
+    /// * `target`: The energy at or below which the run stops
+    pub fn new(target: f64) -> Self {
+        Self { target }
+    }
+}
+
+impl Termination for TargetEnergy {
+    fn check(&mut self, progress: &Progress) -> Option<StopReason> {
+        (progress.best_energy <= self.target).then_some(StopReason::TargetEnergy)
+    }
+}
+
+/// Stops once the best energy has not improved for a number of iterations.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::NoImprovement;
+///
+/// let criterion = NoImprovement::new(5000);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NoImprovement {
+    patience: usize,
+}
+
+impl NoImprovement {
+    /// Creates a new stagnation criterion.
+    ///
+    /// # Parameters
+    ///
+    /// * `patience`: The number of iterations without improvement to tolerate (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patience` is zero.
+    pub fn new(patience: usize) -> Self {
+        assert!(patience > 0, "Patience must be positive");
+        Self { patience }
+    }
+}
+
+impl Termination for NoImprovement {
+    fn check(&mut self, progress: &Progress) -> Option<StopReason> {
+        (progress.iterations_since_improvement >= self.patience).then_some(StopReason::Stagnation)
+    }
+}
+
+/// Stops once the temperature drops below a floor.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::TemperatureFloor;
+///
+/// let criterion = TemperatureFloor::new(1e-3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TemperatureFloor {
+    floor: f64,
+}
+
+impl TemperatureFloor {
+    /// Creates a new temperature floor criterion.
+    ///
+    /// # Parameters
+    ///
+    /// * `floor`: The temperature below which the run stops (must be non-negative)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `floor` is negative.
+    pub fn new(floor: f64) -> Self {
+        assert!(floor >= 0.0, "Temperature floor must be non-negative");
+        Self { floor }
+    }
+}
+
+impl Termination for TemperatureFloor {
+    fn check(&mut self, progress: &Progress) -> Option<StopReason> {
+        (progress.temperature < self.floor).then_some(StopReason::TemperatureFloor)
+    }
+}
+
+/// Stops once a wall-clock budget is exhausted.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::TimeLimit;
+/// use std::time::Duration;
+///
+/// let criterion = TimeLimit::new(Duration::from_secs(60));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimeLimit {
+    budget: Duration,
+}
+
+impl TimeLimit {
+    /// Creates a new wall-clock budget criterion.
+    ///
+    /// # Parameters
+    ///
+    /// * `budget`: The maximum wall-clock duration of the run
+    pub fn new(budget: Duration) -> Self {
+        Self { budget }
+    }
+}
+
+impl Termination for TimeLimit {
+    fn check(&mut self, progress: &Progress) -> Option<StopReason> {
+        (progress.elapsed >= self.budget).then_some(StopReason::TimeLimit)
+    }
+}
+
+/// Stops once a number of energy evaluations has been performed.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::EvaluationBudget;
+///
+/// let criterion = EvaluationBudget::new(1_000_000);
+/// ```
+#[derive(Clone, Debug)]
+pub struct EvaluationBudget {
+    budget: usize,
+}
+
+impl EvaluationBudget {
+    /// Creates a new evaluation budget criterion.
+    ///
+    /// # Parameters
+    ///
+    /// * `budget`: The maximum number of energy evaluations (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    pub fn new(budget: usize) -> Self {
+        assert!(budget > 0, "Evaluation budget must be positive");
+        Self { budget }
+    }
+}
+
+impl Termination for EvaluationBudget {
+    fn check(&mut self, progress: &Progress) -> Option<StopReason> {
+        (progress.evaluations >= self.budget).then_some(StopReason::EvaluationBudget)
+    }
+}
+
+/// Stops the run when cancelled, possibly from another thread.
+///
+/// Tokens are cheap to clone and all clones share the same flag, so one
+/// clone can be registered with `Annealer::with_termination` while another
+/// is kept to cancel the run. The annealer polls the token after every
+/// iteration and stops with `StopReason::Cancelled`.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::CancellationToken;
+/// use frostfire::prelude::*;
+/// use std::thread;
+///
+/// # #[derive(Clone)]
+/// # struct MyState;
+/// # impl State for MyState {
+/// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+/// # }
+/// let token = CancellationToken::new();
+/// let mut annealer = Annealer::new(
+///     MyState,
+///     MyEnergy,
+///     GeometricSchedule::new(100.0, 0.95),
+///     seeded_rng(42),
+///     usize::MAX,
+/// )
+/// .with_termination(token.clone());
+///
+/// let run = thread::spawn(move || annealer.run_with_stats());
+/// token.cancel();
+///
+/// let result = run.join().unwrap();
+/// assert_eq!(result.stop_reason, StopReason::Cancelled);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests that every run polling this token stops.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl Termination for CancellationToken {
+    fn check(&mut self, _progress: &Progress) -> Option<StopReason> {
+        self.is_cancelled().then_some(StopReason::Cancelled)
+    }
+}
+
+/// Detects the frozen regime and stops the `Annealer` early.
+///
+/// The run is frozen once the acceptance ratio stays below `threshold` for
+/// `levels` consecutive temperature levels without any improvement of the
+/// best energy. The run then stops with `StopReason::Frozen`, and
+/// `AnnealingResult::frozen_at` reports the iteration at which the first of
+/// these levels started.
+///
+/// When the annealer runs equilibrium levels (see
+/// `Annealer::with_equilibrium`), each of them is a temperature level.
+/// Otherwise the temperature changes every iteration, and consecutive
+/// iterations are grouped into levels of `level_length` iterations. A reheat
+/// starts the count over.
+///
+/// Register the detector with `Annealer::with_frozen_detection`.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::{FrozenDetection, StopReason};
+/// use frostfire::prelude::*;
+///
+/// # #[derive(Clone)]
+/// # struct MyState(f64);
+/// # impl State for MyState {
+/// #     fn neighbor(&self, rng: &mut impl rand::Rng) -> Self { MyState(self.0 + rng.gen_range(-1.0..1.0)) }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, state: &Self::State) -> f64 { state.0.abs() }
+/// # }
+/// // Stop after 5 levels of 200 iterations with under 1% accepted moves
+/// let mut annealer = Annealer::new(
+///     MyState(10.0),
+///     MyEnergy,
+///     GeometricSchedule::new(10.0, 0.999),
+///     seeded_rng(42),
+///     100_000,
+/// )
+/// .with_frozen_detection(FrozenDetection::new(0.01, 5).with_level_length(200));
+///
+/// let result = annealer.run_with_stats();
+/// assert_eq!(result.stop_reason, StopReason::Frozen);
+/// println!("Froze at iteration {:?}", result.frozen_at);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FrozenDetection {
+    threshold: f64,
+    levels: usize,
+    level_length: usize,
+}
+
+impl FrozenDetection {
+    /// Creates a new frozen-state detector with levels of 100 iterations.
+    ///
+    /// # Parameters
+    ///
+    /// * `threshold`: The acceptance ratio below which a level counts as frozen (between 0 and 1)
+    /// * `levels`: The number of consecutive frozen levels that stop the run (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not in (0, 1] or `levels` is zero.
+    pub fn new(threshold: f64, levels: usize) -> Self {
+        assert!(
+            threshold > 0.0 && threshold <= 1.0,
+            "Acceptance threshold must be in (0, 1]"
+        );
+        assert!(levels > 0, "Level count must be positive");
+        Self {
+            threshold,
+            levels,
+            level_length: 100,
+        }
+    }
+
+    /// Sets the number of iterations per level when the temperature changes every iteration.
+    ///
+    /// This setting is ignored when the annealer runs equilibrium levels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level_length` is zero.
+    pub fn with_level_length(mut self, level_length: usize) -> Self {
+        assert!(level_length > 0, "Level length must be positive");
+        self.level_length = level_length;
+        self
+    }
+
+    /// Returns the number of iterations per level when the temperature changes every iteration.
+    pub fn level_length(&self) -> usize {
+        self.level_length
+    }
+
+    /// Records a completed temperature level.
+    ///
+    /// # Parameters
+    ///
+    /// * `tracker`: The detection state of the run, holding the level's counts
+    /// * `iteration`: The number of completed iterations at the end of the level
+    /// * `last_improvement`: The iteration count at which the best energy last improved
+    ///
+    /// # Returns
+    ///
+    /// The iteration at which the run froze, if it is now frozen.
+    pub(crate) fn record_level(
+        &self,
+        tracker: &mut FrozenTracker,
+        iteration: usize,
+        last_improvement: usize,
+    ) -> Option<usize> {
+        let start = iteration - tracker.level_iterations;
+        let ratio = tracker.level_accepted as f64 / tracker.level_iterations as f64;
+        if ratio < self.threshold && last_improvement <= start {
+            if tracker.frozen_levels == 0 {
+                tracker.frozen_since = start;
+            }
+            tracker.frozen_levels += 1;
+        } else {
+            tracker.frozen_levels = 0;
+        }
+        tracker.level_iterations = 0;
+        tracker.level_accepted = 0;
+
+        (tracker.frozen_levels >= self.levels).then_some(tracker.frozen_since)
+    }
+}
+
+/// The state of frozen-state detection during a run.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FrozenTracker {
+    /// The number of iterations in the level in progress
+    pub(crate) level_iterations: usize,
+    /// The number of accepted moves in the level in progress
+    pub(crate) level_accepted: usize,
+    /// The number of consecutive frozen levels
+    frozen_levels: usize,
+    /// The iteration at which the first of the consecutive frozen levels started
+    frozen_since: usize,
+}
+
+impl Persist for FrozenTracker {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.level_iterations.write_to(writer)?;
+        self.level_accepted.write_to(writer)?;
+        self.frozen_levels.write_to(writer)?;
+        self.frozen_since.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        Ok(Self {
+            level_iterations: usize::read_from(reader)?,
+            level_accepted: usize::read_from(reader)?,
+            frozen_levels: usize::read_from(reader)?,
+            frozen_since: usize::read_from(reader)?,
+        })
+    }
+}
+
+/// Stops when any of its criteria is satisfied, reporting the first one's reason.
+///
+/// Every criterion is checked on each call, so stateful criteria observe the
+/// full progress of the run.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::{AnyOf, EvaluationBudget, TargetEnergy};
+///
+/// let criterion = AnyOf::new()
+///     .with(TargetEnergy::new(0.0))
+///     .with(EvaluationBudget::new(100_000));
+/// ```
+#[derive(Default)]
+pub struct AnyOf {
+    criteria: Vec<Box<dyn Termination>>,
+}
+
+impl AnyOf {
+    /// Creates an empty combinator, which never stops on its own.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a criterion to the combinator.
+    ///
+    /// # Parameters
+    ///
+    /// * `criterion`: The criterion to add
+    ///
+    /// # Returns
+    ///
+    /// The modified combinator.
+    pub fn with(mut self, criterion: impl Termination + 'static) -> Self {
+        self.criteria.push(Box::new(criterion));
+        self
+    }
+}
+
+impl Termination for AnyOf {
+    fn check(&mut self, progress: &Progress) -> Option<StopReason> {
+        self.criteria
+            .iter_mut()
+            .map(|criterion| criterion.check(progress))
+            .fold(None, |first, reason| first.or(reason))
+    }
+}
+
+/// Stops when all of its criteria are satisfied at the same time.
+///
+/// An empty `AllOf` never stops.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::termination::{AllOf, NoImprovement, TemperatureFloor};
+///
+/// // Stop once it is both cold and stagnating
+/// let criterion = AllOf::new()
+///     .with(TemperatureFloor::new(0.01))
+///     .with(NoImprovement::new(1000));
+/// ```
+#[derive(Default)]
+pub struct AllOf {
+    criteria: Vec<Box<dyn Termination>>,
+}
+
+impl AllOf {
+    /// Creates an empty combinator, which never stops on its own.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a criterion to the combinator.
+    ///
+    /// # Parameters
+    ///
+    /// * `criterion`: The criterion to add
+    ///
+    /// # Returns
+    ///
+    /// The modified combinator.
+    pub fn with(mut self, criterion: impl Termination + 'static) -> Self {
+        self.criteria.push(Box::new(criterion));
+        self
+    }
+}
+
+impl Termination for AllOf {
+    fn check(&mut self, progress: &Progress) -> Option<StopReason> {
+        if self.criteria.is_empty() {
+            return None;
+        }
+
+        let reasons: Vec<Option<StopReason>> = self
+            .criteria
+            .iter_mut()
+            .map(|criterion| criterion.check(progress))
+            .collect();
+
+        reasons
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .map(StopReason::All)
+    }
+}
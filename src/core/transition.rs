@@ -1,8 +1,11 @@
 //! Transition policies for simulated annealing.
 //!
 //! This module provides functions to determine whether a proposed state
-//! transition should be accepted during the annealing process.
+//! transition should be accepted during the annealing process, and the
+//! `AcceptanceRule` trait through which the `Annealer` applies them.
 
+use crate::core::energy::Energy;
+use crate::core::state::MoveState;
 use rand::Rng;
 
 /// The classic Metropolis-Hastings acceptance criterion for simulated annealing.
@@ -61,3 +64,192 @@ pub fn accept(delta: f64, temperature: f64, rng: &mut impl Rng) -> bool {
         rng.gen::<f64>() < (-delta / temperature).exp()
     }
 }
+
+/// The `AcceptanceRule` trait decides whether a proposed transition is accepted.
+///
+/// The `Annealer` is generic over its acceptance rule, so different rules can
+/// be compared on the same problem without changing anything else. The
+/// default rule is `Metropolis`.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::transition::{AcceptanceRule, Threshold};
+/// use frostfire::prelude::*;
+///
+/// # #[derive(Clone)]
+/// # struct MyState;
+/// # impl State for MyState {
+/// #     fn neighbor(&self, _: &mut impl rand::Rng) -> Self { self.clone() }
+/// # }
+/// # struct MyEnergy;
+/// # impl Energy for MyEnergy {
+/// #     type State = MyState;
+/// #     fn cost(&self, _: &Self::State) -> f64 { 0.0 }
+/// # }
+/// // Use threshold accepting instead of the Metropolis criterion
+/// let mut annealer = Annealer::new(
+///     MyState,
+///     MyEnergy,
+///     GeometricSchedule::new(1.0, 0.95),
+///     seeded_rng(42),
+///     10000,
+/// )
+/// .with_acceptance(Threshold);
+///
+/// let result = annealer.run_with_stats();
+/// ```
+pub trait AcceptanceRule: Send + Sync {
+    /// Decides whether a transition is accepted.
+    ///
+    /// # Parameters
+    ///
+    /// * `delta`: The energy difference (new_energy - current_energy)
+    /// * `temperature`: The current temperature in the annealing process
+    /// * `rng`: A random number generator
+    ///
+    /// # Returns
+    ///
+    /// `true` if the transition should be accepted, `false` otherwise.
+    fn accept(&self, delta: f64, temperature: f64, rng: &mut impl Rng) -> bool;
+}
+
+/// The Metropolis criterion, accepting with probability min(1, exp(-delta/T)).
+///
+/// This is the classic rule implemented by `accept` and the default rule of
+/// the `Annealer`. Improvements are accepted without drawing a random number.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::transition::{AcceptanceRule, Metropolis};
+/// use frostfire::prelude::*;
+///
+/// let mut rng = seeded_rng(42);
+/// assert!(Metropolis.accept(-1.0, 1.0, &mut rng));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Metropolis;
+
+impl AcceptanceRule for Metropolis {
+    fn accept(&self, delta: f64, temperature: f64, rng: &mut impl Rng) -> bool {
+        accept(delta, temperature, rng)
+    }
+}
+
+/// The Barker (Glauber) criterion, accepting with probability 1 / (1 + exp(delta/T)).
+///
+/// Unlike Metropolis, even improvements are occasionally rejected, and a
+/// move that leaves the energy unchanged is accepted with probability 1/2.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::transition::{AcceptanceRule, Barker};
+/// use frostfire::prelude::*;
+///
+/// let mut rng = seeded_rng(42);
+/// let accepted = (0..10_000)
+///     .filter(|_| Barker.accept(0.0, 1.0, &mut rng))
+///     .count();
+/// assert!(accepted > 4800 && accepted < 5200);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Barker;
+
+impl AcceptanceRule for Barker {
+    fn accept(&self, delta: f64, temperature: f64, rng: &mut impl Rng) -> bool {
+        // exp overflows to infinity for large deltas, giving probability 0
+        let probability = 1.0 / (1.0 + (delta / temperature).exp());
+        rng.gen::<f64>() < probability
+    }
+}
+
+/// Threshold accepting, which accepts every move with delta below the temperature.
+///
+/// The temperature acts as a deterministic threshold (Dueck and Scheuer), so
+/// no random numbers are drawn. Schedules for threshold accepting are usually
+/// on the scale of typical energy differences.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::transition::{AcceptanceRule, Threshold};
+/// use frostfire::prelude::*;
+///
+/// let mut rng = seeded_rng(42);
+/// assert!(Threshold.accept(0.5, 1.0, &mut rng));
+/// assert!(!Threshold.accept(1.5, 1.0, &mut rng));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Threshold;
+
+impl AcceptanceRule for Threshold {
+    fn accept(&self, delta: f64, temperature: f64, _rng: &mut impl Rng) -> bool {
+        delta < temperature
+    }
+}
+
+/// The Metropolis criterion evaluated in log space.
+///
+/// Accepts a worsening move when ln(u) < -delta/T for a uniform u, which is
+/// equivalent to u < exp(-delta/T) but never evaluates `exp`, so extreme
+/// ratios of delta to temperature cannot underflow or overflow.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::core::transition::{AcceptanceRule, LogMetropolis};
+/// use frostfire::prelude::*;
+///
+/// let mut rng = seeded_rng(42);
+/// assert!(LogMetropolis.accept(-1e300, 1e-300, &mut rng));
+/// assert!(!LogMetropolis.accept(1e300, 1e-300, &mut rng));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogMetropolis;
+
+impl AcceptanceRule for LogMetropolis {
+    fn accept(&self, delta: f64, temperature: f64, rng: &mut impl Rng) -> bool {
+        if delta < 0.0 {
+            true
+        } else {
+            rng.gen::<f64>().ln() < -delta / temperature
+        }
+    }
+}
+
+/// Performs a single Metropolis step at a fixed temperature.
+///
+/// Proposes a move, evaluates it through `Energy::delta`, and applies it in
+/// place if the Metropolis criterion accepts it. This is the building block of
+/// the fixed-temperature engines, which sample rather than cool.
+///
+/// # Parameters
+///
+/// * `energy`: The energy function
+/// * `state`: The current state, updated in place on acceptance
+/// * `current_energy`: The energy of `state`, updated on acceptance
+/// * `temperature`: The fixed temperature of the step
+/// * `rng`: A random number generator
+///
+/// # Returns
+///
+/// `true` if the proposed move was accepted, `false` otherwise.
+pub(crate) fn metropolis_step<E: Energy>(
+    energy: &E,
+    state: &mut E::State,
+    current_energy: &mut f64,
+    temperature: f64,
+    rng: &mut impl Rng,
+) -> bool {
+    let mut mv = state.propose_move(rng);
+    let delta = energy.delta(state, &mut mv, *current_energy);
+
+    let accepted = accept(delta, temperature, rng);
+    if accepted {
+        state.apply_move(&mut mv);
+        *current_energy += delta;
+    }
+    accepted
+}
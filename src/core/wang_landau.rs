@@ -0,0 +1,519 @@
+//! Wang–Landau density-of-states estimation.
+//!
+//! The density of states g(E) counts how many states have energy E. Once it is
+//! known, the partition function Z(T) = sum g(E) exp(-E / T) and every
+//! thermodynamic quantity derived from it can be computed at all temperatures
+//! at once. The Wang–Landau algorithm estimates ln g(E) with a random walk in
+//! energy space that is biased until it visits all energies equally often.
+
+use crate::core::energy::Energy;
+use crate::core::state::MoveState;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// A partition of an energy range into equally wide bins.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnergyBins {
+    min: f64,
+    width: f64,
+    count: usize,
+}
+
+impl EnergyBins {
+    /// Divides the energy range [min, max] into `count` equally wide bins.
+    ///
+    /// # Parameters
+    ///
+    /// * `min`: The lower end of the energy range
+    /// * `max`: The upper end of the energy range (must be greater than `min`)
+    /// * `count`: The number of bins (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is not greater than `min` or `count` is zero.
+    pub fn uniform(min: f64, max: f64, count: usize) -> Self {
+        assert!(max > min, "Energy range must not be empty");
+        assert!(count > 0, "Bin count must be positive");
+        Self {
+            min,
+            width: (max - min) / count as f64,
+            count,
+        }
+    }
+
+    /// Creates one bin per level of a discrete energy spectrum.
+    ///
+    /// The levels are `lowest`, `lowest + step`, ..., and each bin is
+    /// centered on its level. This suits models with integer energies, such
+    /// as Ising models and QUBOs with integer coefficients.
+    ///
+    /// # Parameters
+    ///
+    /// * `lowest`: The lowest energy level
+    /// * `step`: The spacing between levels (must be positive)
+    /// * `count`: The number of levels (must be positive)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive or `count` is zero.
+    pub fn discrete(lowest: f64, step: f64, count: usize) -> Self {
+        assert!(step > 0.0, "Level spacing must be positive");
+        assert!(count > 0, "Bin count must be positive");
+        Self {
+            min: lowest - 0.5 * step,
+            width: step,
+            count,
+        }
+    }
+
+    /// Returns the number of bins.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the index of the bin containing an energy, or `None` if the
+    /// energy is outside the range.
+    pub fn index(&self, energy: f64) -> Option<usize> {
+        let position = (energy - self.min) / self.width;
+        if !(0.0..=self.count as f64).contains(&position) {
+            return None;
+        }
+        // The upper end of the range belongs to the last bin
+        Some((position as usize).min(self.count - 1))
+    }
+
+    /// Returns the energy at the center of a bin.
+    pub fn center(&self, index: usize) -> f64 {
+        self.min + (index as f64 + 0.5) * self.width
+    }
+}
+
+/// The density of states estimated by a `WangLandau` run.
+///
+/// The estimate is only known up to a multiplicative constant, so ln g(E) is
+/// only known up to an additive one. Use `normalized` to fix it, for example
+/// so that the total number of states is 2^N for N binary variables.
+/// Thermodynamic averages do not depend on the constant.
+#[derive(Clone, Debug)]
+pub struct DensityOfStates {
+    /// The energy bins
+    pub bins: EnergyBins,
+    /// The estimate of ln g(E) for each bin, with negative infinity for bins
+    /// that were never visited
+    pub log_g: Vec<f64>,
+    /// The number of steps of the random walk
+    pub steps: usize,
+    /// The number of times the histogram was found flat
+    pub flat_histograms: usize,
+    /// The final value of the modification factor ln f
+    pub modification: f64,
+    /// Whether the modification factor reached its final value before the
+    /// step limit
+    pub converged: bool,
+}
+
+impl DensityOfStates {
+    /// Returns the energy at the center of each bin.
+    pub fn energies(&self) -> Vec<f64> {
+        (0..self.bins.count())
+            .map(|i| self.bins.center(i))
+            .collect()
+    }
+
+    /// Returns a copy shifted so that the total number of states is exp(`log_total`).
+    ///
+    /// # Parameters
+    ///
+    /// * `log_total`: The logarithm of the total number of states
+    pub fn normalized(&self, log_total: f64) -> Self {
+        let shift = log_total - log_sum_exp(&self.log_g);
+        let mut normalized = self.clone();
+        for log_g in normalized.log_g.iter_mut() {
+            *log_g += shift;
+        }
+        normalized
+    }
+
+    /// Returns the logarithm of the partition function at a temperature.
+    ///
+    /// The value includes the additive constant of ln g(E).
+    pub fn log_partition(&self, temperature: f64) -> f64 {
+        log_sum_exp(&self.log_weights(temperature))
+    }
+
+    /// Returns the free energy F = -T ln Z at a temperature.
+    ///
+    /// The value includes the constant -T ln c, where c is the unknown factor
+    /// of the density of states; normalize the density first for absolute values.
+    pub fn free_energy(&self, temperature: f64) -> f64 {
+        -temperature * self.log_partition(temperature)
+    }
+
+    /// Returns the mean energy at a temperature.
+    pub fn mean_energy(&self, temperature: f64) -> f64 {
+        self.energy_moments(temperature).0
+    }
+
+    /// Returns the heat capacity C = (<E^2> - <E>^2) / T^2 at a temperature.
+    pub fn heat_capacity(&self, temperature: f64) -> f64 {
+        let (mean, mean_square) = self.energy_moments(temperature);
+        (mean_square - mean * mean) / (temperature * temperature)
+    }
+
+    /// Returns the entropy S = (<E> - F) / T at a temperature.
+    ///
+    /// Like the free energy, the value includes the constant ln c.
+    pub fn entropy(&self, temperature: f64) -> f64 {
+        (self.mean_energy(temperature) - self.free_energy(temperature)) / temperature
+    }
+
+    /// Writes ln g(E) in CSV format, with an `energy,log_g` header and one
+    /// line per visited bin.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: The destination of the CSV data
+    pub fn write_csv(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(writer, "energy,log_g")?;
+        for (energy, log_g) in self.energies().into_iter().zip(&self.log_g) {
+            if log_g.is_finite() {
+                writeln!(writer, "{energy},{log_g}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes ln g(E) to a CSV file, in the format of `write_csv`.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: The path of the file to create
+    pub fn to_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_csv(&mut writer)?;
+        writer.flush()
+    }
+
+    /// The Boltzmann weights ln(g(E) exp(-E / T)) of the visited bins.
+    fn log_weights(&self, temperature: f64) -> Vec<f64> {
+        self.log_g
+            .iter()
+            .enumerate()
+            .filter(|(_, log_g)| log_g.is_finite())
+            .map(|(i, log_g)| log_g - self.bins.center(i) / temperature)
+            .collect()
+    }
+
+    /// Returns <E> and <E^2> at a temperature.
+    fn energy_moments(&self, temperature: f64) -> (f64, f64) {
+        let log_z = self.log_partition(temperature);
+        self.log_g
+            .iter()
+            .enumerate()
+            .filter(|(_, log_g)| log_g.is_finite())
+            .fold((0.0, 0.0), |(mean, mean_square), (i, log_g)| {
+                let energy = self.bins.center(i);
+                let probability = (log_g - energy / temperature - log_z).exp();
+                (
+                    mean + probability * energy,
+                    mean_square + probability * energy * energy,
+                )
+            })
+    }
+}
+
+/// Computes ln(sum(exp(x))) without overflow.
+fn log_sum_exp(values: &[f64]) -> f64 {
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if max == f64::NEG_INFINITY {
+        return max;
+    }
+    max + values.iter().map(|x| (x - max).exp()).sum::<f64>().ln()
+}
+
+/// Wang–Landau engine estimating the density of states of an energy function.
+///
+/// The walk proposes moves with `MoveState::propose_move` (for `State` types,
+/// `State::neighbor`) and accepts a move from energy E to E' with probability
+/// min(1, g(E) / g(E')), using the current estimate of g. Proposals leaving
+/// the energy range are rejected. After every step, ln g of the current bin
+/// is increased by the modification factor ln f and the bin's histogram
+/// count is incremented.
+///
+/// Every `check_interval` steps the histogram is checked for flatness: it is
+/// flat when the count of every visited bin is at least `flatness` times the
+/// mean count. On a flat histogram, ln f is halved and the histogram reset.
+/// Following Belardinelli and Pereyra, once ln f drops below 1/t, where t is
+/// the number of steps divided by the number of bins, ln f is set to 1/t at
+/// every step instead, which removes the saturation error of the original
+/// algorithm. The run ends when ln f reaches its final value or after
+/// `max_steps` steps.
+///
+/// Flatness is judged over the bins visited so far, so the energy range may
+/// include energies the model cannot reach.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::prelude::*;
+/// use rand::Rng;
+///
+/// // Eight independent spins, each up spin costing one unit of energy
+/// #[derive(Clone)]
+/// struct Spins([bool; 8]);
+///
+/// impl State for Spins {
+///     fn neighbor(&self, rng: &mut impl Rng) -> Self {
+///         let mut spins = self.0;
+///         let idx = rng.gen_range(0..spins.len());
+///         spins[idx] = !spins[idx];
+///         Spins(spins)
+///     }
+/// }
+///
+/// struct Field;
+///
+/// impl Energy for Field {
+///     type State = Spins;
+///
+///     fn cost(&self, state: &Spins) -> f64 {
+///         state.0.iter().filter(|&&up| up).count() as f64
+///     }
+/// }
+///
+/// let mut wang_landau = WangLandau::new(
+///     Spins([false; 8]),
+///     Field,
+///     EnergyBins::discrete(0.0, 1.0, 9),
+///     seeded_rng(42),
+///     10_000_000,
+/// )
+/// .with_final_modification(1e-5);
+///
+/// // There are 2^8 states in total
+/// let dos = wang_landau.run().normalized(8.0 * 2.0_f64.ln());
+///
+/// // g(4) is the binomial coefficient C(8, 4) = 70
+/// assert!((dos.log_g[4] - 70.0_f64.ln()).abs() < 0.1);
+/// println!("C(T = 1) = {:.4}", dos.heat_capacity(1.0));
+/// ```
+pub struct WangLandau<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S>,
+{
+    state: S,
+    energy: E,
+    bins: EnergyBins,
+    rng: StdRng,
+    max_steps: usize,
+    flatness: f64,
+    check_interval: usize,
+    initial_modification: f64,
+    final_modification: f64,
+}
+
+impl<S, E> WangLandau<S, E>
+where
+    S: MoveState,
+    E: Energy<State = S>,
+{
+    /// Creates a new Wang–Landau engine.
+    ///
+    /// By default the histogram must reach a flatness of 0.8, checked every
+    /// 10000 steps, and ln f goes from 1 down to 1e-8.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_state`: The starting state of the walk (its energy must lie in `bins`)
+    /// * `energy`: The energy function whose density of states is estimated
+    /// * `bins`: The partition of the energy range
+    /// * `rng`: The random number generator
+    /// * `max_steps`: The maximum number of steps of the walk
+    ///
+    /// # Panics
+    ///
+    /// Panics if the energy of `initial_state` is outside the range of `bins`.
+    pub fn new(
+        initial_state: S,
+        energy: E,
+        bins: EnergyBins,
+        rng: StdRng,
+        max_steps: usize,
+    ) -> Self {
+        assert!(
+            bins.index(energy.cost(&initial_state)).is_some(),
+            "Initial energy must lie within the energy bins"
+        );
+        Self {
+            state: initial_state,
+            energy,
+            bins,
+            rng,
+            max_steps,
+            flatness: 0.8,
+            check_interval: 10000,
+            initial_modification: 1.0,
+            final_modification: 1e-8,
+        }
+    }
+
+    /// Sets the flatness a histogram must reach, as the ratio of its lowest
+    /// count to its mean count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `flatness` is not between 0 and 1 (exclusive).
+    pub fn with_flatness(mut self, flatness: f64) -> Self {
+        assert!(
+            flatness > 0.0 && flatness < 1.0,
+            "Flatness must be between 0 and 1"
+        );
+        self.flatness = flatness;
+        self
+    }
+
+    /// Sets the number of steps between flatness checks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn with_check_interval(mut self, interval: usize) -> Self {
+        assert!(interval > 0, "Check interval must be positive");
+        self.check_interval = interval;
+        self
+    }
+
+    /// Sets the initial modification factor ln f.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modification` is not positive.
+    pub fn with_initial_modification(mut self, modification: f64) -> Self {
+        assert!(modification > 0.0, "Modification factor must be positive");
+        self.initial_modification = modification;
+        self
+    }
+
+    /// Sets the modification factor ln f at which the run ends.
+    ///
+    /// The statistical error of ln g(E) is roughly proportional to the square
+    /// root of this value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modification` is not positive.
+    pub fn with_final_modification(mut self, modification: f64) -> Self {
+        assert!(modification > 0.0, "Modification factor must be positive");
+        self.final_modification = modification;
+        self
+    }
+
+    /// Returns the current state of the walk.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Runs the random walk and estimates the density of states.
+    ///
+    /// # Returns
+    ///
+    /// The estimated density of states, with the statistics of the run.
+    pub fn run(&mut self) -> DensityOfStates {
+        let count = self.bins.count();
+        let mut log_g = vec![0.0_f64; count];
+        let mut histogram = vec![0usize; count];
+        let mut visited = vec![false; count];
+
+        let mut current_energy = self.energy.cost(&self.state);
+        let mut current_bin = self
+            .bins
+            .index(current_energy)
+            .expect("current energy lies within the energy bins");
+
+        let mut modification = self.initial_modification;
+        let mut one_over_t = false;
+        let mut flat_histograms = 0;
+        let mut steps = 0;
+
+        while steps < self.max_steps && modification > self.final_modification {
+            steps += 1;
+
+            let mut mv = self.state.propose_move(&mut self.rng);
+            let delta = self.energy.delta(&mut self.state, &mut mv, current_energy);
+            let new_energy = current_energy + delta;
+            if let Some(new_bin) = self.bins.index(new_energy) {
+                let log_ratio = log_g[current_bin] - log_g[new_bin];
+                if log_ratio >= 0.0 || self.rng.gen::<f64>() < log_ratio.exp() {
+                    self.state.apply_move(&mut mv);
+                    current_energy = new_energy;
+                    current_bin = new_bin;
+                }
+            }
+
+            log_g[current_bin] += modification;
+            histogram[current_bin] += 1;
+            visited[current_bin] = true;
+
+            let time = steps as f64 / count as f64;
+            if one_over_t {
+                modification = 1.0 / time;
+                continue;
+            }
+
+            if steps.is_multiple_of(self.check_interval) && self.is_flat(&histogram, &visited) {
+                flat_histograms += 1;
+                modification /= 2.0;
+                histogram.iter_mut().for_each(|h| *h = 0);
+                if modification <= 1.0 / time {
+                    one_over_t = true;
+                    modification = 1.0 / time;
+                }
+            }
+        }
+
+        // Shift so the lowest visited bin has ln g = 0
+        let lowest = log_g
+            .iter()
+            .zip(&visited)
+            .filter(|(_, &visited)| visited)
+            .map(|(&log_g, _)| log_g)
+            .fold(f64::INFINITY, f64::min);
+        let log_g = log_g
+            .iter()
+            .zip(&visited)
+            .map(|(&log_g, &visited)| {
+                if visited {
+                    log_g - lowest
+                } else {
+                    f64::NEG_INFINITY
+                }
+            })
+            .collect();
+
+        DensityOfStates {
+            bins: self.bins,
+            log_g,
+            steps,
+            flat_histograms,
+            modification,
+            converged: modification <= self.final_modification,
+        }
+    }
+
+    /// Returns whether the histogram of the visited bins is flat.
+    fn is_flat(&self, histogram: &[usize], visited: &[bool]) -> bool {
+        let counts: Vec<usize> = histogram
+            .iter()
+            .zip(visited)
+            .filter(|(_, &visited)| visited)
+            .map(|(&count, _)| count)
+            .collect();
+        let mean = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+        let min = counts.iter().copied().min().unwrap_or(0);
+        min as f64 >= self.flatness * mean
+    }
+}
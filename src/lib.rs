@@ -19,9 +19,13 @@
 //! ## Core Components
 //!
 //! - `State`: Represents a candidate solution in the search space
+//! - `MoveState`: A candidate solution mutated in place by lightweight moves
 //! - `Energy`: Defines the cost function to be minimized
 //! - `Schedule`: Controls the cooling process during annealing
 //! - `Annealer`: The main engine that performs the optimization
+//! - `ParallelTempering`: A replica exchange engine running a ladder of fixed temperatures
+//! - `PopulationAnnealing`: Anneals a resampled population and estimates free energies
+//! - `MultiStart`: Runs many independent annealers in parallel and keeps the best
 //!
 //! ## Example
 //!
@@ -80,9 +84,12 @@ pub mod utils;
 // Re-export core components for convenient access
 pub use crate::core::annealer::Annealer;
 pub use crate::core::energy::Energy;
+pub use crate::core::multistart::MultiStart;
+pub use crate::core::population::PopulationAnnealing;
 pub use crate::core::schedule::{
-    AdaptiveSchedule, GeometricSchedule, LogarithmicSchedule, Schedule,
+    AdaptiveSchedule, Feedback, GeometricSchedule, LogarithmicSchedule, Schedule,
 };
-pub use crate::core::state::State;
-pub use crate::core::transition;
-pub use crate::rng::seeded_rng::seeded_rng;
+pub use crate::core::state::{MoveState, State};
+pub use crate::core::tempering::ParallelTempering;
+pub use crate::core::transition::{self, AcceptanceRule};
+pub use crate::rng::seeded_rng::{derived_rng, seeded_rng};
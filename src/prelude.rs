@@ -3,15 +3,35 @@
 //! This module re-exports the most commonly used items from the frostfire crate,
 //! allowing users to import them all with a single `use frostfire::prelude::*` statement.
 
+pub use crate::core::ais::{AisResult, AnnealedImportanceSampling};
 pub use crate::core::annealer::{Annealer, AnnealingResult};
+pub use crate::core::calibration::TemperatureCalibration;
+pub use crate::core::combinator::ScheduleExt;
 pub use crate::core::energy::Energy;
+pub use crate::core::equilibrium::{Equilibrium, LevelStats};
+pub use crate::core::multiobjective::{
+    MultiEnergy, MultiObjectiveAnnealer, MultiObjectiveResult, ParetoArchive, ParetoPoint,
+};
+pub use crate::core::multistart::{MultiStart, MultiStartResult};
+pub use crate::core::observer::{IterationRecord, Observer, SharedBest};
+pub use crate::core::population::{PopulationAnnealing, PopulationResult, PopulationStep};
+pub use crate::core::reheat::ReheatPolicy;
+pub use crate::core::sampler::{MetropolisSampler, SampleStats, Samples};
 pub use crate::core::schedule::{
-    AdaptiveSchedule, GeometricSchedule, LogarithmicSchedule, Schedule,
+    AdaptiveSchedule, CauchySchedule, CosineSchedule, Decay, ExponentialSchedule, Feedback,
+    GeometricSchedule, HuangSchedule, Interpolation, LamDelosmeSchedule, LinearSchedule,
+    LogarithmicSchedule, LundyMeesSchedule, QuadraticSchedule, Schedule, TabulatedSchedule,
+    WallClockSchedule,
 };
-pub use crate::core::state::State;
-pub use crate::core::transition::accept;
-pub use crate::rng::seeded_rng::seeded_rng;
+pub use crate::core::state::{MoveState, State};
+pub use crate::core::tabu::TabuList;
+pub use crate::core::tempering::{ParallelTempering, TemperingResult};
+pub use crate::core::termination::{CancellationToken, FrozenDetection, StopReason, Termination};
+pub use crate::core::transition::{accept, AcceptanceRule, Metropolis};
+pub use crate::core::wang_landau::{DensityOfStates, EnergyBins, WangLandau};
+pub use crate::rng::seeded_rng::{derived_rng, seeded_rng};
 
 // Re-export commonly used external types
 pub use rand::rngs::StdRng;
 pub use rand::Rng;
+pub use rand_chacha::ChaCha12Rng;
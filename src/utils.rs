@@ -132,3 +132,121 @@ pub fn boltzmann_probability(delta: f64, temperature: f64) -> f64 {
         (-delta / temperature).exp()
     }
 }
+
+/// Calculates the normalized autocorrelation of a series at a lag.
+///
+/// # Parameters
+///
+/// * `values`: A series of f64 values, such as an observable recorded along a Markov chain
+/// * `lag`: The lag at which to measure the correlation
+///
+/// # Returns
+///
+/// The autocorrelation, between -1 and 1, or 0.0 if the lag is not smaller
+/// than the series length or the series is constant. The autocorrelation at
+/// lag 0 of a non-constant series is 1.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::utils::autocorrelation;
+///
+/// let alternating = [1.0, -1.0, 1.0, -1.0, 1.0, -1.0];
+/// assert_eq!(autocorrelation(&alternating, 0), 1.0);
+/// assert!(autocorrelation(&alternating, 1) < -0.8);
+/// ```
+pub fn autocorrelation(values: &[f64], lag: usize) -> f64 {
+    let n = values.len();
+    if lag >= n {
+        return 0.0;
+    }
+
+    let avg = average(values);
+    let variance = values.iter().map(|&x| (x - avg).powi(2)).sum::<f64>();
+    if variance == 0.0 {
+        return 0.0;
+    }
+
+    let covariance = values
+        .iter()
+        .zip(&values[lag..])
+        .map(|(&x, &y)| (x - avg) * (y - avg))
+        .sum::<f64>();
+    covariance / variance
+}
+
+/// Estimates the integrated autocorrelation time of a series.
+///
+/// The integrated autocorrelation time is tau = 1 + 2 * sum(rho(t)) over
+/// lags t >= 1, the number of consecutive values it takes to get one
+/// effectively independent value. The sum is truncated with Sokal's automatic
+/// window, at the first lag M with M >= 5 * tau(M), which keeps the noise of
+/// large lags out of the estimate.
+///
+/// # Parameters
+///
+/// * `values`: A series of f64 values, such as an observable recorded along a Markov chain
+///
+/// # Returns
+///
+/// The estimated autocorrelation time, at least 1.0. Series with fewer than 2
+/// values or no variation yield 1.0.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::utils::integrated_autocorrelation_time;
+///
+/// // A slowly varying series is strongly correlated
+/// let slow: Vec<f64> = (0..1000).map(|i| (i as f64 / 50.0).sin()).collect();
+/// assert!(integrated_autocorrelation_time(&slow) > 5.0);
+/// ```
+pub fn integrated_autocorrelation_time(values: &[f64]) -> f64 {
+    let n = values.len();
+    if n < 2 {
+        return 1.0;
+    }
+
+    let avg = average(values);
+    let deviations: Vec<f64> = values.iter().map(|&x| x - avg).collect();
+    let variance = deviations.iter().map(|d| d * d).sum::<f64>();
+    if variance == 0.0 {
+        return 1.0;
+    }
+
+    let mut tau = 1.0;
+    for lag in 1..n {
+        let covariance = deviations
+            .iter()
+            .zip(&deviations[lag..])
+            .map(|(x, y)| x * y)
+            .sum::<f64>();
+        tau += 2.0 * covariance / variance;
+        if lag as f64 >= 5.0 * tau {
+            break;
+        }
+    }
+    tau.max(1.0)
+}
+
+/// Estimates the effective sample size of a correlated series.
+///
+/// # Parameters
+///
+/// * `values`: A series of f64 values, such as an observable recorded along a Markov chain
+///
+/// # Returns
+///
+/// The number of values divided by their integrated autocorrelation time.
+///
+/// # Examples
+///
+/// ```
+/// use frostfire::utils::effective_sample_size;
+///
+/// let slow: Vec<f64> = (0..1000).map(|i| (i as f64 / 50.0).sin()).collect();
+/// assert!(effective_sample_size(&slow) < 200.0);
+/// ```
+pub fn effective_sample_size(values: &[f64]) -> f64 {
+    values.len() as f64 / integrated_autocorrelation_time(values)
+}
@@ -0,0 +1,103 @@
+//! Tests for the pluggable acceptance rules.
+//!
+//! These tests verify that the default rule reproduces the classic
+//! Metropolis behavior, that the log-space variant makes the same decisions,
+//! and that every rule can drive the annealer to a good solution.
+
+mod common;
+
+use common::PointState;
+use frostfire::core::transition::{Barker, LogMetropolis, Threshold};
+use frostfire::prelude::*;
+
+// Seed for reproducibility
+const SEED: u64 = 8080;
+
+/// A rugged energy landscape with its global minimum 0 at the origin.
+struct RuggedEnergy;
+
+impl Energy for RuggedEnergy {
+    type State = PointState;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        state
+            .coords
+            .iter()
+            .map(|&x| x * x + 2.0 * (1.0 - (4.0 * x).cos()))
+            .sum()
+    }
+}
+
+fn annealer() -> Annealer<PointState, RuggedEnergy, GeometricSchedule> {
+    Annealer::new(
+        PointState {
+            coords: vec![2.5, -2.0, 1.5],
+        },
+        RuggedEnergy,
+        GeometricSchedule::new(5.0, 0.9997),
+        seeded_rng(SEED),
+        30_000,
+    )
+}
+
+#[test]
+fn test_default_rule_is_metropolis() {
+    let default = annealer().run_with_stats();
+    let explicit = annealer().with_acceptance(Metropolis).run_with_stats();
+
+    assert_eq!(default.final_state, explicit.final_state);
+    assert_eq!(default.accepted_moves, explicit.accepted_moves);
+    assert_eq!(default.best_energy, explicit.best_energy);
+}
+
+#[test]
+fn test_log_metropolis_matches_metropolis() {
+    let metropolis = annealer().run_with_stats();
+    let log_space = annealer().with_acceptance(LogMetropolis).run_with_stats();
+
+    // Both rules draw the same random numbers and make the same decisions
+    assert_eq!(metropolis.final_state, log_space.final_state);
+    assert_eq!(metropolis.accepted_moves, log_space.accepted_moves);
+}
+
+#[test]
+fn test_all_rules_find_good_solutions() {
+    let metropolis = annealer().run_with_stats();
+    let barker = annealer().with_acceptance(Barker).run_with_stats();
+    let threshold = annealer().with_acceptance(Threshold).run_with_stats();
+
+    for (name, result) in [
+        ("Metropolis", &metropolis),
+        ("Barker", &barker),
+        ("Threshold", &threshold),
+    ] {
+        println!(
+            "{}: best energy {}, accepted {}",
+            name, result.best_energy, result.accepted_moves
+        );
+        assert!(
+            result.best_energy < 0.1,
+            "{} failed to converge, got {}",
+            name,
+            result.best_energy
+        );
+    }
+
+    // Barker rejects some improvements, so it accepts fewer moves than Metropolis
+    assert!(barker.accepted_moves < metropolis.accepted_moves);
+}
+
+#[test]
+fn test_rules_at_extreme_ratios() {
+    let mut rng = seeded_rng(SEED);
+
+    // Tiny temperatures and huge deltas must not produce NaN decisions
+    assert!(!LogMetropolis.accept(1e308, 1e-308, &mut rng));
+    assert!(LogMetropolis.accept(-1e308, 1e-308, &mut rng));
+    assert!(!Barker.accept(1e308, 1e-308, &mut rng));
+    assert!(Barker.accept(-1e308, 1e-308, &mut rng));
+
+    // Threshold accepting is deterministic
+    assert!(Threshold.accept(0.99, 1.0, &mut rng));
+    assert!(!Threshold.accept(1.0, 1.0, &mut rng));
+}
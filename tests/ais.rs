@@ -0,0 +1,113 @@
+//! Tests for annealed importance sampling.
+//!
+//! These tests compare the estimated partition function ratio of a spin system
+//! with its exact value, check the weight diagnostics, and verify that results
+//! for a given seed do not depend on the number of threads.
+
+mod common;
+
+use common::{exact_log_z, FieldEnergy, Spins};
+use frostfire::prelude::*;
+use rand::Rng;
+
+// Seed for reproducibility
+const SEED: u64 = 1380;
+
+/// Number of spins in the test system
+const SPINS: usize = 12;
+
+/// The initial temperature of every test schedule
+const INITIAL_TEMP: f64 = 5.0;
+
+/// Exact Boltzmann samples at the initial temperature.
+fn initial_samples(count: usize) -> Vec<Spins> {
+    let weight = (-1.0 / INITIAL_TEMP).exp();
+    let up = weight / (1.0 + weight);
+    let mut rng = seeded_rng(SEED);
+    (0..count)
+        .map(|_| Spins((0..SPINS).map(|_| rng.gen_bool(up)).collect()))
+        .collect()
+}
+
+fn ais(chains: usize, alpha: f64, steps: usize) -> AnnealedImportanceSampling<Spins, FieldEnergy> {
+    AnnealedImportanceSampling::new(
+        initial_samples(chains),
+        FieldEnergy,
+        GeometricSchedule::new(INITIAL_TEMP, alpha),
+        steps,
+        SEED,
+    )
+    .with_sweeps(SPINS)
+}
+
+#[test]
+fn test_ais_estimates_exact_partition_ratio() {
+    let result = ais(400, 0.95, 60).with_threads(4).run();
+
+    assert_eq!(result.temperatures.len(), 61);
+    assert_eq!(result.temperatures[0], INITIAL_TEMP);
+    assert_eq!(result.log_weights.len(), 400);
+    assert_eq!(result.states.len(), 400);
+    assert!(result.acceptance_rate > 0.0 && result.acceptance_rate < 1.0);
+
+    let t_final = *result.temperatures.last().unwrap();
+    let exact = exact_log_z(SPINS, t_final) - exact_log_z(SPINS, INITIAL_TEMP);
+    let estimate = result.log_partition_ratio();
+    assert!(
+        (estimate - exact).abs() < 4.0 * result.log_ratio_std_error() + 0.02,
+        "Estimated ln Z ratio {estimate} +/- {}, exact {exact}",
+        result.log_ratio_std_error()
+    );
+
+    let log_z = result.log_partition(exact_log_z(SPINS, INITIAL_TEMP));
+    assert!((log_z - exact_log_z(SPINS, t_final)).abs() < 0.1);
+    assert!((result.free_energy(exact_log_z(SPINS, INITIAL_TEMP)) + t_final * log_z).abs() < 1e-12);
+
+    for (state, &energy) in result.states.iter().zip(&result.energies) {
+        assert_eq!(FieldEnergy.cost(state), energy);
+    }
+}
+
+#[test]
+fn test_ais_weight_diagnostics() {
+    let slow = ais(200, 0.98, 150).run();
+    let fast = ais(200, 0.6, 8).run();
+
+    for result in [&slow, &fast] {
+        let ess = result.effective_sample_size();
+        assert!(ess > 0.0 && ess <= 200.0 + 1e-9);
+        assert!(result.weight_variance() >= -1e-9);
+        assert!((result.weight_variance() - (200.0 / ess - 1.0)).abs() < 1e-9);
+    }
+
+    assert!(
+        slow.effective_sample_size() > fast.effective_sample_size(),
+        "Slower annealing should give more even weights ({} vs {})",
+        slow.effective_sample_size(),
+        fast.effective_sample_size()
+    );
+    assert!(slow.log_ratio_std_error() < fast.log_ratio_std_error());
+}
+
+#[test]
+fn test_ais_is_deterministic_across_thread_counts() {
+    let single = ais(50, 0.9, 20).with_threads(1).run();
+    let multi = ais(50, 0.9, 20).with_threads(3).run();
+
+    assert_eq!(single.log_weights, multi.log_weights);
+    assert_eq!(single.states, multi.states);
+    assert_eq!(single.energies, multi.energies);
+    assert_eq!(single.acceptance_rate, multi.acceptance_rate);
+}
+
+#[test]
+#[should_panic(expected = "At least one initial state is required")]
+fn test_ais_rejects_empty_initial_states() {
+    AnnealedImportanceSampling::new(
+        Vec::<Spins>::new(),
+        FieldEnergy,
+        GeometricSchedule::new(INITIAL_TEMP, 0.9),
+        10,
+        SEED,
+    );
+}
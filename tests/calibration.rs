@@ -0,0 +1,129 @@
+//! Tests for the initial temperature calibration.
+//!
+//! These tests verify that the calibrated temperature achieves the target
+//! acceptance probability for uphill moves, both on a landscape where it is
+//! known in closed form and on a continuous landscape.
+
+mod common;
+
+use common::{AbsEnergy, IntegerState};
+use frostfire::core::calibration::{CalibrationMethod, TemperatureCalibration};
+use frostfire::prelude::*;
+use rand::Rng;
+
+// Seed for reproducibility
+const SEED: u64 = 4711;
+
+/// A point confined to the cube [-1, 1]^n, so random walks stay near the bowl.
+#[derive(Clone)]
+struct BoundedPoint {
+    coords: Vec<f64>,
+}
+
+impl State for BoundedPoint {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        let mut coords = self.coords.clone();
+        let idx = rng.gen_range(0..coords.len());
+        coords[idx] = (coords[idx] + rng.gen_range(-0.5..0.5)).clamp(-1.0, 1.0);
+        Self { coords }
+    }
+}
+
+/// A scaled quadratic bowl, so the right temperature is far from 1.
+struct ScaledBowl;
+
+impl Energy for ScaledBowl {
+    type State = BoundedPoint;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        1000.0 * state.coords.iter().map(|x| x * x).sum::<f64>()
+    }
+}
+
+/// Measures the fraction of uphill moves accepted at a temperature on a fresh walk.
+fn uphill_acceptance(initial_state: &BoundedPoint, temperature: f64, seed: u64) -> f64 {
+    let mut rng = seeded_rng(seed);
+    let mut state = initial_state.clone();
+    let mut probability_sum = 0.0;
+    let mut uphill = 0;
+
+    for _ in 0..5000 {
+        let next = state.neighbor(&mut rng);
+        let delta = ScaledBowl.cost(&next) - ScaledBowl.cost(&state);
+        if delta > 0.0 {
+            probability_sum += (-delta / temperature).exp();
+            uphill += 1;
+        }
+        state = next;
+    }
+
+    probability_sum / uphill as f64
+}
+
+#[test]
+fn test_calibration_with_constant_delta() {
+    // Far from the origin, every uphill move costs exactly 1, so the
+    // acceptance probability is exp(-1/T) and T = -1 / ln(0.8)
+    let expected = -1.0 / 0.8_f64.ln();
+
+    for method in [CalibrationMethod::Kirkpatrick, CalibrationMethod::BenAmeur] {
+        let temperature = TemperatureCalibration::new(0.8)
+            .with_method(method)
+            .calibrate(&AbsEnergy, &IntegerState(10_000), &mut seeded_rng(SEED))
+            .unwrap();
+
+        println!("{:?}: {}", method, temperature);
+        assert!((temperature - expected).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_ben_ameur_achieves_target_acceptance() {
+    let initial_state = BoundedPoint {
+        coords: vec![0.5, -0.5, 0.25],
+    };
+
+    for target in [0.5, 0.8, 0.95] {
+        let calibration = TemperatureCalibration::new(target).with_samples(5000);
+        let ben_ameur = calibration
+            .calibrate(&ScaledBowl, &initial_state, &mut seeded_rng(SEED))
+            .unwrap();
+        let kirkpatrick = calibration
+            .with_method(CalibrationMethod::Kirkpatrick)
+            .calibrate(&ScaledBowl, &initial_state, &mut seeded_rng(SEED))
+            .unwrap();
+
+        let measured = uphill_acceptance(&initial_state, ben_ameur, SEED + 1);
+        println!(
+            "target {}: Ben-Ameur T = {} (acceptance {}), Kirkpatrick T = {}",
+            target, ben_ameur, measured, kirkpatrick
+        );
+
+        assert!(
+            (measured - target).abs() < 0.05,
+            "Acceptance {} missed target {}",
+            measured,
+            target
+        );
+
+        // The mean-delta estimate overshoots when deltas vary
+        assert!(kirkpatrick > ben_ameur);
+    }
+}
+
+#[test]
+fn test_calibration_on_flat_landscape() {
+    struct Flat;
+
+    impl Energy for Flat {
+        type State = IntegerState;
+
+        fn cost(&self, _state: &Self::State) -> f64 {
+            0.0
+        }
+    }
+
+    let temperature =
+        TemperatureCalibration::new(0.8).calibrate(&Flat, &IntegerState(0), &mut seeded_rng(SEED));
+    assert_eq!(temperature, None);
+}
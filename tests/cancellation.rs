@@ -0,0 +1,101 @@
+//! Tests for cooperative cancellation and live best-solution snapshots.
+//!
+//! These tests verify that a run can be stopped from another thread, and
+//! that the shared best handle tracks the best solution while the run is in
+//! progress.
+
+mod common;
+
+use common::LineState;
+use frostfire::prelude::*;
+use std::thread;
+use std::time::Duration;
+
+// Seed for reproducibility
+const SEED: u64 = 31337;
+
+/// A quadratic bowl with its minimum at x = 3.
+struct BowlEnergy;
+
+impl Energy for BowlEnergy {
+    type State = LineState;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        (state.0 - 3.0).powi(2)
+    }
+}
+
+fn annealer(max_iters: usize) -> Annealer<LineState, BowlEnergy, GeometricSchedule> {
+    Annealer::new(
+        LineState(-20.0),
+        BowlEnergy,
+        GeometricSchedule::new(5.0, 0.9999),
+        seeded_rng(SEED),
+        max_iters,
+    )
+}
+
+#[test]
+fn test_cancel_from_another_thread() {
+    let token = CancellationToken::new();
+    let best = SharedBest::new();
+    let mut annealer = annealer(usize::MAX)
+        .with_termination(token.clone())
+        .with_observer(best.clone());
+
+    let run = thread::spawn(move || annealer.run_with_stats());
+
+    // Wait until the run has made real progress, reading the best live
+    while best.best_energy().is_none_or(|energy| energy > 1.0) {
+        thread::sleep(Duration::from_millis(1));
+    }
+    let (live_state, live_energy) = best.snapshot().expect("run has started");
+    assert!(live_energy <= 1.0);
+    assert!((BowlEnergy.cost(&live_state) - live_energy).abs() < 1e-9);
+
+    token.cancel();
+    let result = run.join().unwrap();
+
+    assert!(token.is_cancelled());
+    assert_eq!(result.stop_reason, StopReason::Cancelled);
+    assert!(result.best_energy <= live_energy);
+
+    // After the run, the handle holds the final best solution
+    assert_eq!(best.best_energy(), Some(result.best_energy));
+    assert_eq!(best.best_state(), Some(result.best_state));
+}
+
+#[test]
+fn test_shared_best_starts_with_initial_state() {
+    let best = SharedBest::new();
+    assert_eq!(best.best_energy(), None);
+    assert!(best.snapshot().is_none());
+
+    // With no iterations the best is the initial state
+    let mut annealer = annealer(0).with_observer(best.clone());
+    let result = annealer.run_with_stats();
+
+    assert_eq!(result.iterations, 0);
+    assert_eq!(best.best_state(), Some(LineState(-20.0)));
+    assert_eq!(best.best_energy(), Some(529.0));
+}
+
+#[test]
+fn test_cancelled_token_stops_immediately() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    // The token is checked before the first iteration
+    let result = annealer(10_000)
+        .with_termination(token.clone())
+        .run_with_stats();
+    assert_eq!(result.stop_reason, StopReason::Cancelled);
+    assert_eq!(result.iterations, 0);
+    assert_eq!(result.evaluations, 1);
+
+    let mut stepped = annealer(10_000).with_termination(token);
+    assert!(stepped.step().is_none());
+    let result = stepped.finish();
+    assert_eq!(result.stop_reason, StopReason::Cancelled);
+    assert_eq!(result.iterations, 0);
+}
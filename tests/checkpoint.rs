@@ -0,0 +1,227 @@
+//! Tests for checkpointing and resuming annealing runs.
+//!
+//! These tests verify that a run interrupted and resumed from a checkpoint
+//! finishes bit-for-bit identically to one that never stopped and wrote no
+//! checkpoints, including runs with equilibrium levels and a tabu memory, that
+//! observers of a resumed run receive the restored best state, that writing
+//! checkpoints does not change a run, and that invalid checkpoint data is
+//! rejected.
+
+mod common;
+
+use common::PointState;
+use frostfire::prelude::*;
+use rand::SeedableRng;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Seed for reproducibility
+const SEED: u64 = 1234;
+
+/// A rugged energy landscape with many local minima.
+struct RuggedEnergy;
+
+impl Energy for RuggedEnergy {
+    type State = PointState;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        state
+            .coords
+            .iter()
+            .map(|&x| x * x + 3.0 * (1.0 - (3.0 * x).cos()))
+            .sum()
+    }
+}
+
+/// Checkpoints need a generator that can save its position.
+type CheckpointedAnnealer =
+    Annealer<PointState, RuggedEnergy, AdaptiveSchedule, Metropolis, ChaCha12Rng>;
+
+/// Returns a checkpoint path unique to this test process.
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("frostfire-{}-{}.ckpt", name, std::process::id()))
+}
+
+/// An annealer without checkpoints, serving as the uninterrupted reference.
+fn plain_annealer(max_iters: usize) -> CheckpointedAnnealer {
+    Annealer::new(
+        PointState {
+            coords: vec![3.0, -2.5, 4.0],
+        },
+        RuggedEnergy,
+        AdaptiveSchedule::new(10.0),
+        ChaCha12Rng::seed_from_u64(SEED),
+        max_iters,
+    )
+    .with_reheat(ReheatPolicy::on_stagnation(400, 0.5))
+}
+
+fn annealer(max_iters: usize, path: &Path) -> CheckpointedAnnealer {
+    plain_annealer(max_iters).with_checkpoints(path, 250)
+}
+
+#[test]
+fn test_resume_matches_uninterrupted_run() {
+    let path = checkpoint_path("resume");
+
+    // The reference run never stops and writes no checkpoints
+    let reference = plain_annealer(3000).run_with_stats();
+
+    // This run is interrupted after 1100 iterations; its last checkpoint is at 1000
+    let interrupted = annealer(1100, &path).run_with_stats();
+    assert_eq!(interrupted.iterations, 1100);
+
+    let mut resumed = annealer(3000, &path)
+        .resume_from(&path)
+        .expect("failed to load checkpoint");
+    let result = resumed.run_with_stats();
+    std::fs::remove_file(&path).ok();
+
+    println!("Reference: {:?}", reference);
+    println!("Resumed: {:?}", result);
+
+    assert_eq!(result.iterations, reference.iterations);
+    assert_eq!(result.evaluations, reference.evaluations);
+    assert_eq!(result.accepted_moves, reference.accepted_moves);
+    assert_eq!(result.rejected_moves, reference.rejected_moves);
+    assert_eq!(result.reheats, reference.reheats);
+    assert_eq!(result.best_state, reference.best_state);
+    assert_eq!(
+        result.best_energy.to_bits(),
+        reference.best_energy.to_bits()
+    );
+    assert_eq!(result.final_state, reference.final_state);
+    assert_eq!(
+        result.final_energy.to_bits(),
+        reference.final_energy.to_bits()
+    );
+    assert_eq!(result.final_temp.to_bits(), reference.final_temp.to_bits());
+}
+
+#[test]
+fn test_resume_preserves_equilibrium_levels() {
+    let path = checkpoint_path("levels");
+    let equilibrium = || Equilibrium::energy_stability(40, 0.5, 300);
+
+    let reference = plain_annealer(3000)
+        .with_equilibrium(equilibrium())
+        .run_with_stats();
+
+    annealer(1100, &path)
+        .with_equilibrium(equilibrium())
+        .run_with_stats();
+    let result = annealer(3000, &path)
+        .with_equilibrium(equilibrium())
+        .resume_from(&path)
+        .expect("failed to load checkpoint")
+        .run_with_stats();
+    std::fs::remove_file(&path).ok();
+
+    assert!(reference.levels.len() > 5);
+    assert_eq!(result.levels, reference.levels);
+    assert_eq!(result.iterations, reference.iterations);
+    assert_eq!(result.best_state, reference.best_state);
+    assert_eq!(
+        result.best_energy.to_bits(),
+        reference.best_energy.to_bits()
+    );
+    assert_eq!(result.final_state, reference.final_state);
+    assert_eq!(result.final_temp.to_bits(), reference.final_temp.to_bits());
+}
+
+#[test]
+fn test_resume_preserves_tabu_memory() {
+    let path = checkpoint_path("tabu");
+    // The coordinate a move changed may not be moved again for a while
+    let tabu = || {
+        TabuList::on_moves(25, |current: &PointState, next: &PointState| {
+            current
+                .coords
+                .iter()
+                .zip(&next.coords)
+                .position(|(a, b)| a != b)
+                .unwrap_or(0)
+        })
+    };
+
+    let reference = plain_annealer(3000).with_tabu(tabu()).run_with_stats();
+
+    // Interrupt the run while it is still hot, so the memory affects which moves are accepted
+    annealer(300, &path).with_tabu(tabu()).run_with_stats();
+    let result = annealer(3000, &path)
+        .with_tabu(tabu())
+        .resume_from(&path)
+        .expect("failed to load checkpoint")
+        .run_with_stats();
+    std::fs::remove_file(&path).ok();
+
+    assert!(reference.tabu_rejections > 0);
+    assert_eq!(result.tabu_rejections, reference.tabu_rejections);
+    assert_eq!(result.accepted_moves, reference.accepted_moves);
+    assert_eq!(result.evaluations, reference.evaluations);
+    assert_eq!(result.best_state, reference.best_state);
+    assert_eq!(
+        result.best_energy.to_bits(),
+        reference.best_energy.to_bits()
+    );
+    assert_eq!(result.final_state, reference.final_state);
+}
+
+#[test]
+fn test_resume_seeds_shared_best() {
+    let path = checkpoint_path("shared-best");
+    annealer(1100, &path).run_with_stats();
+
+    // The checkpoint was written at iteration 1000, so no iterations are left
+    let best = SharedBest::new();
+    let result = plain_annealer(1000)
+        .with_observer(best.clone())
+        .resume_from(&path)
+        .expect("failed to load checkpoint")
+        .run_with_stats();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(result.iterations, 1000);
+    assert_eq!(
+        best.snapshot(),
+        Some((result.best_state, result.best_energy))
+    );
+}
+
+#[test]
+fn test_checkpoints_do_not_change_the_run() {
+    let path = checkpoint_path("unchanged");
+
+    let plain = plain_annealer(2000).run_with_stats();
+    let checkpointed = annealer(2000, &path).run_with_stats();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(checkpointed.best_state, plain.best_state);
+    assert_eq!(
+        checkpointed.best_energy.to_bits(),
+        plain.best_energy.to_bits()
+    );
+    assert_eq!(checkpointed.final_state, plain.final_state);
+    assert_eq!(checkpointed.accepted_moves, plain.accepted_moves);
+}
+
+#[test]
+fn test_invalid_checkpoint_is_rejected() {
+    let path = checkpoint_path("invalid");
+    std::fs::write(&path, b"not a checkpoint").unwrap();
+
+    let error = annealer(100, &path)
+        .resume_from(&path)
+        .err()
+        .expect("invalid checkpoint was accepted");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    // Truncated data is reported as an unexpected end of file
+    let mut truncated: &[u8] = b"FFCK\x03\x00\x00\x00\x05";
+    let error = annealer(100, &path)
+        .read_checkpoint(&mut truncated)
+        .expect_err("truncated checkpoint was accepted");
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,151 @@
+//! Problems shared by the integration tests: random walks on the integer
+//! line, the real line and in higher dimensions for the annealer tests, the
+//! Rastrigin function for the parallel engines, and a spin model with an exact partition
+//! function for the tests of the sampling and free-energy engines.
+
+// Each test crate uses a different subset of these helpers
+#![allow(dead_code)]
+
+use frostfire::core::checkpoint::Persist;
+use frostfire::prelude::*;
+use rand::Rng;
+use std::f64::consts::PI;
+use std::io::{self, Read, Write};
+
+/// A point on the integer line, where every move changes the energy by 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntegerState(pub i64);
+
+impl State for IntegerState {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        if rng.gen_bool(0.5) {
+            Self(self.0 + 1)
+        } else {
+            Self(self.0 - 1)
+        }
+    }
+}
+
+/// Absolute distance to the origin.
+pub struct AbsEnergy;
+
+impl Energy for AbsEnergy {
+    type State = IntegerState;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        state.0.abs() as f64
+    }
+}
+
+/// An annealer walking from 50 towards the origin, cooling geometrically from 10.
+pub fn integer_annealer(
+    seed: u64,
+    max_iters: usize,
+) -> Annealer<IntegerState, AbsEnergy, GeometricSchedule> {
+    Annealer::new(
+        IntegerState(50),
+        AbsEnergy,
+        GeometricSchedule::new(10.0, 0.999),
+        seeded_rng(seed),
+        max_iters,
+    )
+}
+
+/// A point on the real line, moving up to one unit per step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineState(pub f64);
+
+impl State for LineState {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        Self(self.0 + rng.gen_range(-1.0..1.0))
+    }
+}
+
+/// A point in a multi-dimensional space, moving along one axis per step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointState {
+    pub coords: Vec<f64>,
+}
+
+impl State for PointState {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        let mut coords = self.coords.clone();
+        let idx = rng.gen_range(0..coords.len());
+        coords[idx] += rng.gen_range(-0.5..0.5);
+        Self { coords }
+    }
+}
+
+impl Persist for PointState {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.coords.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        Ok(Self {
+            coords: Vec::read_from(reader)?,
+        })
+    }
+}
+
+/// A point in the Rastrigin function domain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RastriginPoint {
+    pub coords: Vec<f64>,
+}
+
+impl State for RastriginPoint {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        let mut coords = self.coords.clone();
+        let idx = rng.gen_range(0..coords.len());
+        coords[idx] = (coords[idx] + rng.gen_range(-0.5..0.5)).clamp(-5.12, 5.12);
+        Self { coords }
+    }
+}
+
+/// The Rastrigin function, with its global minimum 0 at the origin.
+#[derive(Clone)]
+pub struct RastriginEnergy;
+
+impl Energy for RastriginEnergy {
+    type State = RastriginPoint;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        10.0 * state.coords.len() as f64
+            + state
+                .coords
+                .iter()
+                .map(|&x| x * x - 10.0 * (2.0 * PI * x).cos())
+                .sum::<f64>()
+    }
+}
+
+/// A configuration of independent spins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spins(pub Vec<bool>);
+
+impl State for Spins {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        let mut spins = self.0.clone();
+        let idx = rng.gen_range(0..spins.len());
+        spins[idx] = !spins[idx];
+        Spins(spins)
+    }
+}
+
+/// Each up spin costs one unit of energy, so for n spins g(E) = C(n, E) and
+/// Z(T) = (1 + exp(-1/T))^n.
+pub struct FieldEnergy;
+
+impl Energy for FieldEnergy {
+    type State = Spins;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        state.0.iter().filter(|&&up| up).count() as f64
+    }
+}
+
+/// The exact logarithm of the partition function of `spins` spins at a temperature.
+pub fn exact_log_z(spins: usize, temperature: f64) -> f64 {
+    spins as f64 * (1.0 + (-1.0 / temperature).exp()).ln()
+}
@@ -3,9 +3,9 @@
 //! This test verifies that the annealing process exhibits proper convergence behavior,
 //! specifically checking for monotonic decrease in energy over a moving average of iterations.
 
-use frostfire::core::transition;
 use frostfire::prelude::*;
 use rand::Rng;
+use std::sync::{Arc, Mutex};
 
 /// A simple quadratic function state for convergence testing.
 ///
@@ -116,121 +116,31 @@ impl EnergyTracker {
     }
 }
 
-/// A custom schedule for tracking energy at each temperature change.
-#[allow(dead_code)]
-struct TrackingSchedule<Sch: Schedule> {
-    inner: Sch,
-    tracker: EnergyTracker,
-    current_energy: f64,
-}
-
-impl EnergyTracker {
-    fn take(&mut self) -> Self {
-        std::mem::take(self)
-    }
-}
-
-impl<Sch: Schedule> TrackingSchedule<Sch> {
-    #[allow(dead_code)]
-    fn new(schedule: Sch) -> Self {
-        Self {
-            inner: schedule,
-            tracker: EnergyTracker::new(),
-            current_energy: f64::INFINITY,
-        }
-    }
-
-    #[allow(dead_code)]
-    fn update_energy(&mut self, energy: f64) {
-        self.current_energy = energy;
-        self.tracker.add(energy);
-    }
-
-    #[allow(dead_code)]
-    fn tracker(&self) -> &EnergyTracker {
-        &self.tracker
-    }
-}
-
-impl<Sch: Schedule> Schedule for TrackingSchedule<Sch> {
-    fn initial_temp(&self) -> f64 {
-        self.inner.initial_temp()
-    }
-
-    fn next_temp(&self, current_temp: f64, iteration: usize) -> f64 {
-        self.inner.next_temp(current_temp, iteration)
+/// Records the energy of every accepted state.
+impl Observer<QuadraticState> for EnergyTracker {
+    fn on_accept(&mut self, _iteration: usize, _state: &QuadraticState, energy: f64) {
+        self.add(energy);
     }
 }
 
-/// A custom annealer that tracks energy at each iteration.
-struct TrackingAnnealer<S, E, Sch>
-where
-    S: State,
-    E: Energy<State = S>,
-    Sch: Schedule,
-{
-    inner: Annealer<S, E, Sch>,
-    tracker: EnergyTracker,
-}
-
-impl<S, E, Sch> TrackingAnnealer<S, E, Sch>
-where
-    S: State,
-    E: Energy<State = S>,
-    Sch: Schedule,
-{
-    fn new(annealer: Annealer<S, E, Sch>) -> Self {
-        Self {
-            inner: annealer,
-            tracker: EnergyTracker::new(),
-        }
-    }
-
-    fn run(&mut self) -> (S, f64, EnergyTracker) {
-        // Implementation similar to Annealer::run_with_stats but tracking energy at each step
-        let initial_temp = self.inner.schedule.initial_temp();
-        let mut current_temp = initial_temp;
-        let mut current_state = self.inner.state.clone();
-        let mut current_energy = self.inner.energy.cost(&current_state);
-
-        // Track initial energy
-        self.tracker.add(current_energy);
-
-        // Save the initial state as the best state
-        let mut best_state = current_state.clone();
-        let mut best_energy = current_energy;
-
-        // Main annealing loop
-        for i in 0..self.inner.max_iters {
-            // Generate a neighboring state
-            let new_state = current_state.neighbor(&mut self.inner.rng);
-            let new_energy = self.inner.energy.cost(&new_state);
-
-            // Calculate the energy difference
-            let delta = new_energy - current_energy;
-
-            // Decide whether to accept the new state
-            if transition::accept(delta, current_temp, &mut self.inner.rng) {
-                // Accept the new state
-                current_state = new_state;
-                current_energy = new_energy;
-
-                // Track energy at each accepted step
-                self.tracker.add(current_energy);
-
-                // Update the best state if we found a better one
-                if new_energy < best_energy {
-                    best_state = current_state.clone();
-                    best_energy = new_energy;
-                }
-            }
-
-            // Update the temperature according to the cooling schedule
-            current_temp = self.inner.schedule.next_temp(current_temp, i);
-        }
-
-        (best_state, best_energy, self.tracker.take())
-    }
+/// Runs the annealer while tracking the energy at each accepted step.
+fn run_tracked<Sch: Schedule + 'static>(
+    initial_state: QuadraticState,
+    schedule: Sch,
+    rng: StdRng,
+    max_iters: usize,
+) -> (QuadraticState, f64, EnergyTracker) {
+    // Track the initial energy before any move is accepted
+    let mut tracker = EnergyTracker::new();
+    tracker.add(QuadraticEnergy.cost(&initial_state));
+    let tracker = Arc::new(Mutex::new(tracker));
+
+    let mut annealer = Annealer::new(initial_state, QuadraticEnergy, schedule, rng, max_iters)
+        .with_observer(tracker.clone());
+    let (best_state, best_energy) = annealer.run();
+
+    let tracker = std::mem::take(&mut *tracker.lock().unwrap());
+    (best_state, best_energy, tracker)
 }
 
 #[test]
@@ -239,14 +149,10 @@ fn test_convergence_monotonic() {
     let dimensions = 10;
     let mut rng = seeded_rng(42);
     let initial_state = QuadraticState::new(dimensions, 10.0, &mut rng);
-    let energy = QuadraticEnergy;
 
-    // Create a tracking annealer
+    // Run with an observer tracking the energy
     let schedule = GeometricSchedule::new(10.0, 0.95);
-    let annealer = Annealer::new(initial_state, energy, schedule, rng, 5000);
-
-    let mut tracking_annealer = TrackingAnnealer::new(annealer);
-    let (best_state, best_energy, tracker) = tracking_annealer.run();
+    let (best_state, best_energy, tracker) = run_tracked(initial_state, schedule, rng, 5000);
 
     println!("Best energy: {}", best_energy);
     println!("Number of energy samples: {}", tracker.energy_values.len());
@@ -279,37 +185,25 @@ fn test_convergence_different_schedules() {
     let dimensions = 5;
     let mut rng = seeded_rng(42);
     let initial_state = QuadraticState::new(dimensions, 10.0, &mut rng);
-    let energy = QuadraticEnergy;
 
     // Run with geometric schedule
     let geo_schedule = GeometricSchedule::new(10.0, 0.95);
-    let geo_annealer = Annealer::new(
+    let (_, geo_energy, geo_tracker) = run_tracked(
         initial_state.clone(),
-        energy.clone(),
         geo_schedule,
         seeded_rng(42), // Same seed for fair comparison
         3000,
     );
-    let mut tracking_geo = TrackingAnnealer::new(geo_annealer);
-    let (_, geo_energy, geo_tracker) = tracking_geo.run();
 
     // Run with logarithmic schedule
     let log_schedule = LogarithmicSchedule::new(10.0);
-    let log_annealer = Annealer::new(
-        initial_state.clone(),
-        energy.clone(),
-        log_schedule,
-        seeded_rng(42),
-        3000,
-    );
-    let mut tracking_log = TrackingAnnealer::new(log_annealer);
-    let (_, log_energy, log_tracker) = tracking_log.run();
+    let (_, log_energy, log_tracker) =
+        run_tracked(initial_state.clone(), log_schedule, seeded_rng(42), 3000);
 
     // Run with adaptive schedule
     let adp_schedule = AdaptiveSchedule::new(10.0);
-    let adp_annealer = Annealer::new(initial_state, energy, adp_schedule, seeded_rng(42), 3000);
-    let mut tracking_adp = TrackingAnnealer::new(adp_annealer);
-    let (_, adp_energy, adp_tracker) = tracking_adp.run();
+    let (_, adp_energy, adp_tracker) =
+        run_tracked(initial_state, adp_schedule, seeded_rng(42), 3000);
 
     println!("Geometric schedule final energy: {}", geo_energy);
     println!("Logarithmic schedule final energy: {}", log_energy);
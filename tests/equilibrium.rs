@@ -0,0 +1,103 @@
+//! Tests for equilibrium-driven temperature levels.
+//!
+//! These tests verify that each temperature level runs until its
+//! equilibrium criterion or length cap, that the schedule advances once per
+//! level, and that the per-level statistics add up to the run's totals.
+
+mod common;
+
+use common::LineState;
+use frostfire::prelude::*;
+
+// Seed for reproducibility
+const SEED: u64 = 2024;
+
+/// A double well with minima near -2 and 2.
+struct DoubleWell;
+
+impl Energy for DoubleWell {
+    type State = LineState;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        let x = state.0;
+        (x * x - 4.0).powi(2) + 0.5 * x
+    }
+}
+
+fn annealer(equilibrium: Equilibrium) -> Annealer<LineState, DoubleWell, GeometricSchedule> {
+    Annealer::new(
+        LineState(6.0),
+        DoubleWell,
+        GeometricSchedule::new(20.0, 0.8),
+        seeded_rng(SEED),
+        20_000,
+    )
+    .with_equilibrium(equilibrium)
+}
+
+#[test]
+fn test_accepted_move_levels() {
+    let result = annealer(Equilibrium::accepted_moves(40, 300)).run_with_stats();
+    let levels = &result.levels;
+    assert!(levels.len() > 10);
+
+    // Every level but the last ends at its criterion or its length cap
+    for level in &levels[..levels.len() - 1] {
+        if level.equilibrated {
+            assert_eq!(level.accepted_moves, 40);
+            assert!(level.iterations <= 300);
+        } else {
+            assert_eq!(level.iterations, 300);
+            assert!(level.accepted_moves < 40);
+        }
+    }
+    assert!(levels.iter().any(|level| level.equilibrated));
+    assert!(levels.iter().any(|level| !level.equilibrated));
+
+    // The schedule advances once per level
+    for (k, level) in levels.iter().enumerate() {
+        let expected = 20.0 * 0.8_f64.powi(k as i32);
+        assert!((level.temperature - expected).abs() < 1e-9 * expected);
+    }
+
+    // The levels account for the whole run
+    let iterations: usize = levels.iter().map(|level| level.iterations).sum();
+    let accepted: usize = levels.iter().map(|level| level.accepted_moves).sum();
+    assert_eq!(iterations, result.iterations);
+    assert_eq!(accepted, result.accepted_moves);
+    assert_eq!(levels.last().unwrap().best_energy, result.best_energy);
+    assert_eq!(result.final_temp, levels.last().unwrap().temperature);
+}
+
+#[test]
+fn test_energy_stability_levels() {
+    let result = annealer(Equilibrium::energy_stability(50, 0.5, 1000)).run_with_stats();
+    let levels = &result.levels;
+    assert!(levels.len() > 10);
+
+    // Levels end after a whole number of windows, and no sooner than two
+    for level in &levels[..levels.len() - 1] {
+        assert!(level.iterations.is_multiple_of(50));
+        assert!(level.iterations >= 100);
+        assert!(level.equilibrated || level.iterations == 1000);
+    }
+
+    // The energy and its fluctuations shrink as the system cools
+    let first = levels.first().unwrap();
+    let last = levels.last().unwrap();
+    assert!(last.mean_energy < first.mean_energy);
+    assert!(last.energy_variance < first.energy_variance);
+    assert!(last.acceptance_ratio() < first.acceptance_ratio());
+}
+
+#[test]
+fn test_levels_are_empty_without_equilibrium() {
+    let mut annealer = Annealer::new(
+        LineState(6.0),
+        DoubleWell,
+        GeometricSchedule::new(20.0, 0.99),
+        seeded_rng(SEED),
+        1000,
+    );
+    assert!(annealer.run_with_stats().levels.is_empty());
+}
@@ -0,0 +1,186 @@
+//! Tests for multi-objective simulated annealing.
+//!
+//! These tests verify that the annealer approximates known Pareto fronts,
+//! that the archive stays non-dominated and bounded while keeping the
+//! extremes of the front, and that runs are reproducible for a given seed.
+
+use frostfire::core::multiobjective::dominates;
+use frostfire::prelude::*;
+use rand::Rng;
+
+// Seed for reproducibility
+const SEED: u64 = 1729;
+
+/// A point in the unit hypercube.
+#[derive(Clone, Debug, PartialEq)]
+struct UnitPoint(Vec<f64>);
+
+impl State for UnitPoint {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        let mut coords = self.0.clone();
+        let idx = rng.gen_range(0..coords.len());
+        coords[idx] = (coords[idx] + rng.gen_range(-0.1..0.1)).clamp(0.0, 1.0);
+        UnitPoint(coords)
+    }
+}
+
+/// The ZDT1 benchmark, whose Pareto front is f2 = 1 - sqrt(f1) with every
+/// coordinate but the first at zero.
+struct Zdt1;
+
+impl MultiEnergy for Zdt1 {
+    type State = UnitPoint;
+
+    fn objectives(&self) -> usize {
+        2
+    }
+
+    fn costs(&self, state: &UnitPoint) -> Vec<f64> {
+        let f1 = state.0[0];
+        let g = 1.0 + 9.0 * state.0[1..].iter().sum::<f64>() / (state.0.len() - 1) as f64;
+        vec![f1, g * (1.0 - (f1 / g).sqrt())]
+    }
+}
+
+/// An energy that reports the wrong number of objectives.
+struct Inconsistent;
+
+impl MultiEnergy for Inconsistent {
+    type State = UnitPoint;
+
+    fn objectives(&self) -> usize {
+        3
+    }
+
+    fn costs(&self, state: &UnitPoint) -> Vec<f64> {
+        vec![state.0[0]]
+    }
+}
+
+fn zdt1_annealer(capacity: usize) -> MultiObjectiveAnnealer<UnitPoint, Zdt1, GeometricSchedule> {
+    MultiObjectiveAnnealer::new(
+        UnitPoint(vec![0.5; 4]),
+        Zdt1,
+        GeometricSchedule::new(1.0, 0.9995),
+        seeded_rng(SEED),
+        30000,
+    )
+    .with_archive_capacity(capacity)
+}
+
+#[test]
+fn test_multiobjective_approximates_zdt1_front() {
+    let result = zdt1_annealer(30).run();
+
+    assert_eq!(result.iterations, 30000);
+    assert_eq!(result.accepted_moves + result.rejected_moves, 30000);
+    assert!(result.front.len() > 10 && result.front.len() <= 30);
+    assert_eq!(Zdt1.costs(&result.final_state), result.final_objectives);
+
+    for point in &result.front {
+        assert_eq!(Zdt1.costs(&point.state), point.objectives);
+        let (f1, f2) = (point.objectives[0], point.objectives[1]);
+        assert!(
+            f2 - (1.0 - f1.sqrt()) < 0.1,
+            "Point ({f1}, {f2}) should be close to the true front"
+        );
+    }
+
+    // The front should cover most of the range of the first objective
+    let f1_min = result.front.first().unwrap().objectives[0];
+    let f1_max = result.front.last().unwrap().objectives[0];
+    assert!(
+        f1_max - f1_min > 0.7,
+        "Front spans only [{f1_min}, {f1_max}]"
+    );
+}
+
+#[test]
+fn test_multiobjective_front_is_non_dominated_and_sorted() {
+    let result = zdt1_annealer(15).run();
+
+    for a in &result.front {
+        for b in &result.front {
+            assert!(!dominates(&a.objectives, &b.objectives));
+        }
+    }
+    for pair in result.front.windows(2) {
+        assert!(pair[0].objectives[0] <= pair[1].objectives[0]);
+    }
+}
+
+#[test]
+fn test_archive_prunes_most_crowded_points() {
+    let mut archive = ParetoArchive::new(4);
+    assert!(archive.is_empty());
+    assert_eq!(archive.capacity(), 4);
+
+    // Points on the front f2 = 10 - f1, crowded around f1 = 5
+    for (name, f1) in [("a", 0.0), ("b", 4.9), ("c", 5.0), ("d", 5.1), ("e", 10.0)] {
+        assert!(archive.insert(name, vec![f1, 10.0 - f1]));
+    }
+    assert_eq!(archive.len(), 4);
+
+    let front = archive.clone().into_front();
+    let names: Vec<&str> = front.iter().map(|point| point.state).collect();
+    assert_eq!(names.first(), Some(&"a"));
+    assert_eq!(names.last(), Some(&"e"));
+    assert!(
+        !names.contains(&"c"),
+        "The middle of the cluster should be pruned"
+    );
+
+    // Dominated and duplicate points are rejected
+    assert!(!archive.insert("f", vec![5.0, 6.0]));
+    assert!(!archive.insert("g", vec![0.0, 10.0]));
+
+    // A dominating point replaces the points it dominates
+    assert!(archive.insert("h", vec![4.0, 4.0]));
+    assert!(archive
+        .points()
+        .iter()
+        .all(|point| point.state != "b" && point.state != "d"));
+}
+
+#[test]
+fn test_archive_prunes_crowded_extremes_with_three_objectives() {
+    let mut archive = ParetoArchive::new(3);
+
+    // Every point is extreme in some objective: "a" and "b" in one each, "c"
+    // and "d" in two. Of "a" and "b", "b" sits next to "d" in every objective.
+    for (name, objectives) in [
+        ("a", vec![6.0, 8.0, 7.0]),
+        ("b", vec![2.0, 9.0, 1.0]),
+        ("c", vec![7.0, 3.0, 3.0]),
+        ("d", vec![1.0, 10.0, 2.0]),
+    ] {
+        assert!(archive.insert(name, objectives));
+    }
+
+    let mut names: Vec<&str> = archive.points().iter().map(|point| point.state).collect();
+    names.sort();
+    assert_eq!(names, vec!["a", "c", "d"]);
+}
+
+#[test]
+fn test_multiobjective_is_reproducible() {
+    let first = zdt1_annealer(20).run();
+    let second = zdt1_annealer(20).run();
+
+    assert_eq!(first.front, second.front);
+    assert_eq!(first.final_state, second.final_state);
+    assert_eq!(first.accepted_moves, second.accepted_moves);
+}
+
+#[test]
+#[should_panic(expected = "Energy returned the wrong number of objectives")]
+fn test_multiobjective_checks_objective_count() {
+    MultiObjectiveAnnealer::new(
+        UnitPoint(vec![0.5; 2]),
+        Inconsistent,
+        GeometricSchedule::new(1.0, 0.99),
+        seeded_rng(SEED),
+        10,
+    )
+    .run();
+}
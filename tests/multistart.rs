@@ -0,0 +1,86 @@
+//! Tests for the parallel multi-start runner.
+//!
+//! These tests verify that independent restarts improve on a single run of a
+//! rugged landscape, that the reported distribution matches the runs, and
+//! that results are identical for a given seed regardless of thread count.
+
+mod common;
+
+use common::{RastriginEnergy, RastriginPoint};
+use frostfire::core::termination::NoImprovement;
+use frostfire::prelude::*;
+use rand::Rng;
+
+// Seed for reproducibility
+const SEED: u64 = 2718;
+
+/// Draws a uniformly random starting point.
+fn random_point(_run: usize, rng: &mut StdRng) -> RastriginPoint {
+    RastriginPoint {
+        coords: (0..3).map(|_| rng.gen_range(-5.12..5.12)).collect(),
+    }
+}
+
+fn multistart(runs: usize, threads: usize) -> MultiStart<RastriginEnergy, GeometricSchedule> {
+    MultiStart::new(
+        RastriginEnergy,
+        GeometricSchedule::new(2.0, 0.995),
+        2000,
+        runs,
+        SEED,
+    )
+    .with_threads(threads)
+}
+
+#[test]
+fn test_multistart_finds_better_minimum() {
+    let result = multistart(16, 4).run(random_point);
+
+    println!("Best energy: {}", result.best.best_energy);
+    println!("Best run: {}", result.best_run);
+    println!(
+        "Mean energy: {} ± {}",
+        result.mean_energy(),
+        result.energy_std_dev()
+    );
+
+    assert_eq!(result.best_energies.len(), 16);
+    assert_eq!(
+        result.best.best_energy,
+        result.best_energies[result.best_run]
+    );
+    assert!(result
+        .best_energies
+        .iter()
+        .all(|&energy| energy >= result.best.best_energy));
+
+    // The best of many restarts beats the typical run
+    assert!(result.best.best_energy <= result.mean_energy());
+    assert!(result.energy_std_dev() > 0.0);
+}
+
+#[test]
+fn test_multistart_deterministic_across_threads() {
+    let single = multistart(10, 1).run(random_point);
+    let multi = multistart(10, 3).run(random_point);
+
+    assert_eq!(single.best_energies, multi.best_energies);
+    assert_eq!(single.best_run, multi.best_run);
+    assert_eq!(single.best.best_state, multi.best.best_state);
+
+    // Each run draws its own stream, so the runs differ from one another
+    assert!(single
+        .best_energies
+        .windows(2)
+        .any(|pair| pair[0] != pair[1]));
+}
+
+#[test]
+fn test_multistart_applies_setup_to_every_run() {
+    let result = multistart(6, 2)
+        .with_setup(|annealer| annealer.with_termination(NoImprovement::new(200)))
+        .run(random_point);
+
+    assert_eq!(result.best.stop_reason, StopReason::Stagnation);
+    assert!(result.best.iterations < 2000);
+}
@@ -0,0 +1,166 @@
+//! Tests for the population annealing engine.
+//!
+//! These tests verify that population annealing finds the ground state of a
+//! small spin system, estimates its free energy against the exact partition
+//! function, keeps the best state visited during the sweeps, reports sensible
+//! resampling statistics, and produces identical results for a given seed
+//! regardless of the number of threads.
+
+mod common;
+
+use common::{exact_log_z, FieldEnergy, Spins};
+use frostfire::prelude::*;
+use rand::Rng;
+
+// Seed for reproducibility
+const SEED: u64 = 2718;
+
+/// Number of spins in the test system
+const SPINS: usize = 12;
+
+/// A counter that always moves up by one.
+#[derive(Clone, Debug, PartialEq)]
+struct Counter(i64);
+
+impl State for Counter {
+    fn neighbor(&self, _rng: &mut impl Rng) -> Self {
+        Counter(self.0 + 1)
+    }
+}
+
+/// The distance from 3, so counters starting at 0 pass their minimum and leave it.
+struct DistanceFromThree;
+
+impl Energy for DistanceFromThree {
+    type State = Counter;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        (state.0 - 3).abs() as f64
+    }
+}
+
+/// A geometric schedule from `high` down to `low`.
+fn cooling(high: f64, low: f64, count: usize) -> Vec<f64> {
+    let ratio = (low / high).powf(1.0 / (count - 1) as f64);
+    (0..count).map(|k| high * ratio.powi(k as i32)).collect()
+}
+
+fn population(threads: usize) -> PopulationAnnealing<Spins, FieldEnergy> {
+    PopulationAnnealing::new(
+        Spins(vec![true; SPINS]),
+        FieldEnergy,
+        cooling(50.0, 0.2, 40),
+        500,
+        SEED,
+    )
+    .with_sweeps(SPINS)
+    .with_threads(threads)
+}
+
+#[test]
+fn test_population_finds_ground_state() {
+    let result = population(4).run();
+
+    assert_eq!(result.best_energy, 0.0);
+    assert_eq!(result.best_state, Spins(vec![false; SPINS]));
+    assert_eq!(result.states.len(), 500);
+    assert_eq!(result.energies.len(), 500);
+
+    let last = result.steps.last().unwrap();
+    assert!(
+        last.mean_energy < 1.0,
+        "Final mean energy {} should be near the ground state",
+        last.mean_energy
+    );
+}
+
+#[test]
+fn test_population_free_energy_matches_exact() {
+    let temperatures = cooling(50.0, 0.2, 40);
+    let result = population(4).run();
+
+    let free_energies = result.free_energies(exact_log_z(SPINS, temperatures[0]));
+    assert_eq!(free_energies.len(), temperatures.len());
+    for (&temperature, &estimate) in temperatures.iter().zip(&free_energies) {
+        let exact = -temperature * exact_log_z(SPINS, temperature);
+        assert!(
+            (estimate - exact).abs() < 0.05 * exact.abs().max(1.0),
+            "At T = {temperature}: estimated F = {estimate}, exact F = {exact}"
+        );
+    }
+
+    let ratio = exact_log_z(SPINS, temperatures[39]) - exact_log_z(SPINS, temperatures[0]);
+    assert!(
+        (result.log_partition_ratio - ratio).abs() < 0.3,
+        "Estimated ln Z ratio {} should be close to {ratio}",
+        result.log_partition_ratio
+    );
+}
+
+#[test]
+fn test_population_statistics_are_sensible() {
+    let result = population(4).run();
+
+    assert_eq!(result.steps.len(), 40);
+    assert_eq!(result.steps[0].effective_size, 500.0);
+    assert_eq!(result.steps[0].log_weight_mean, 0.0);
+
+    let mut previous_families = 500;
+    for step in &result.steps {
+        assert!(step.effective_size > 0.0 && step.effective_size <= 500.0 + 1e-9);
+        assert!((0.0..=1.0).contains(&step.acceptance_rate));
+        assert!(step.min_energy <= step.mean_energy);
+
+        // Families can only die out, never come back
+        assert!(step.families >= 1 && step.families <= previous_families);
+        previous_families = step.families;
+        assert!(step.family_entropy >= 0.0);
+        assert!(step.family_entropy <= (step.families as f64).ln() + 1e-9);
+    }
+
+    let last = result.steps.last().unwrap();
+    assert!(
+        last.families < 500,
+        "Resampling should have eliminated some families"
+    );
+}
+
+#[test]
+fn test_population_is_deterministic_across_thread_counts() {
+    let single = population(1).run();
+    let multi = population(3).run();
+
+    assert_eq!(single.best_energy, multi.best_energy);
+    assert_eq!(single.states, multi.states);
+    assert_eq!(single.energies, multi.energies);
+    assert_eq!(single.steps, multi.steps);
+    assert_eq!(single.log_partition_ratio, multi.log_partition_ratio);
+}
+
+#[test]
+fn test_population_keeps_best_state_visited_mid_sweep() {
+    // At this temperature every move is accepted, so each member walks from
+    // 0 to 10 and ends with energy 7, having passed energy 0 at 3
+    for threads in [1, 2] {
+        let result = PopulationAnnealing::new(Counter(0), DistanceFromThree, vec![1e12], 4, SEED)
+            .with_sweeps(10)
+            .with_threads(threads)
+            .run();
+
+        assert_eq!(result.energies, vec![7.0; 4]);
+        assert_eq!(result.best_energy, 0.0);
+        assert_eq!(result.best_state, Counter(3));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Temperatures must be strictly decreasing")]
+fn test_population_rejects_increasing_temperatures() {
+    PopulationAnnealing::new(
+        Spins(vec![true; SPINS]),
+        FieldEnergy,
+        vec![1.0, 2.0],
+        10,
+        SEED,
+    );
+}
@@ -326,3 +326,62 @@ fn test_rastrigin_adaptive() {
         result.best_energy
     );
 }
+
+#[test]
+fn test_rastrigin_reheating() {
+    // 5D Rastrigin from a random point in the full range, where a single
+    // cooling pass tends to freeze in a local basin
+    let dimensions = 5;
+    let range = (-5.12, 5.12);
+    let mut rng = seeded_rng(SEED);
+    let initial_state = RastriginState::new(dimensions, range, &mut rng);
+    let energy = RastriginEnergy;
+
+    // Without reheating
+    let mut plain = Annealer::new(
+        initial_state.clone(),
+        energy.clone(),
+        GeometricSchedule::new(20.0, 0.999),
+        seeded_rng(SEED),
+        60000,
+    );
+    let plain_result = plain.run_with_stats();
+
+    // Restart from the best state whenever the search stagnates
+    let mut reheated = Annealer::new(
+        initial_state.clone(),
+        energy.clone(),
+        GeometricSchedule::new(20.0, 0.999),
+        seeded_rng(SEED),
+        60000,
+    )
+    .with_reheat(ReheatPolicy::restart_from_best(3000, 0.5));
+    let reheated_result = reheated.run_with_stats();
+
+    println!("Plain best energy: {}", plain_result.best_energy);
+    println!(
+        "Reheated best energy: {} ({} reheats)",
+        reheated_result.best_energy, reheated_result.reheats
+    );
+
+    assert_eq!(plain_result.reheats, 0);
+    assert!(reheated_result.reheats > 0, "No reheat was triggered");
+    assert!(
+        reheated_result.best_energy <= plain_result.best_energy,
+        "Reheating made the result worse"
+    );
+
+    // Warm restarts happen after 1000, 1000 + 2000 and 3000 + 4000 iterations
+    let mut warm = Annealer::new(
+        initial_state,
+        energy,
+        GeometricSchedule::new(20.0, 0.99),
+        seeded_rng(SEED),
+        10000,
+    )
+    .with_reheat(ReheatPolicy::warm_restarts(1000, 2.0));
+    let warm_result = warm.run_with_stats();
+
+    assert_eq!(warm_result.reheats, 3);
+    assert!((warm_result.final_temp - 20.0 * 0.99_f64.powi(3000)).abs() < 1e-9);
+}
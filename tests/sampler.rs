@@ -0,0 +1,123 @@
+//! Tests for the fixed-temperature Metropolis sampler.
+//!
+//! These tests verify that the sampler reproduces exact Boltzmann averages,
+//! that burn-in and thinning behave as documented, and that the
+//! autocorrelation diagnostics agree with a process of known correlation.
+
+mod common;
+
+use common::{FieldEnergy, Spins};
+use frostfire::prelude::*;
+use frostfire::utils::{effective_sample_size, integrated_autocorrelation_time};
+use rand::Rng;
+
+// Seed for reproducibility
+const SEED: u64 = 1618;
+
+/// Number of spins in the test system
+const SPINS: usize = 10;
+
+/// The exact mean energy of the spin system at a temperature.
+fn exact_mean_energy(temperature: f64) -> f64 {
+    let weight = (-1.0 / temperature).exp();
+    SPINS as f64 * weight / (1.0 + weight)
+}
+
+fn sampler(temperature: f64) -> MetropolisSampler<Spins, FieldEnergy> {
+    MetropolisSampler::new(
+        Spins(vec![true; SPINS]),
+        FieldEnergy,
+        temperature,
+        seeded_rng(SEED),
+    )
+}
+
+#[test]
+fn test_sampler_matches_boltzmann_averages() {
+    for temperature in [0.5, 1.0, 3.0] {
+        let mut sampler = sampler(temperature).with_burn_in(500).with_thinning(5);
+        let samples = sampler.sample(20000);
+
+        assert_eq!(samples.len(), 20000);
+        assert_eq!(samples.energies.len(), 20000);
+        assert_eq!(samples.temperature, temperature);
+
+        let stats = samples.energy_stats();
+        let exact = exact_mean_energy(temperature);
+        assert!(
+            (stats.mean - exact).abs() < 5.0 * stats.standard_error,
+            "At T = {temperature}: mean energy {} +/- {}, exact {exact}",
+            stats.mean,
+            stats.standard_error
+        );
+
+        // The recorded energies belong to the recorded states
+        for (state, &energy) in samples.values.iter().zip(&samples.energies) {
+            assert_eq!(FieldEnergy.cost(state), energy);
+        }
+    }
+}
+
+#[test]
+fn test_sampler_projects_observables_and_thins() {
+    let mut dense = sampler(1.0).with_burn_in(500);
+    let mut sparse = sampler(1.0).with_burn_in(500).with_thinning(20);
+
+    let magnetization = |spins: &Spins| spins.0.iter().filter(|&&up| up).count() as f64;
+    let dense_stats = dense.sample_with(5000, magnetization).stats(|&m| m);
+    let sparse_samples = sparse.sample_with(5000, magnetization);
+    let sparse_stats = sparse_samples.stats(|&m| m);
+
+    assert_eq!(sparse_samples.thinning, 20);
+    assert!(sparse_samples.acceptance_rate > 0.0 && sparse_samples.acceptance_rate < 1.0);
+    assert!(
+        dense_stats.autocorrelation_time > 2.0 * sparse_stats.autocorrelation_time,
+        "Thinning should reduce the autocorrelation time ({} vs {})",
+        dense_stats.autocorrelation_time,
+        sparse_stats.autocorrelation_time
+    );
+    assert!(sparse_stats.effective_sample_size > dense_stats.effective_sample_size);
+    assert!(sparse_stats.effective_sample_size <= 5000.0);
+}
+
+#[test]
+fn test_sampler_burns_in_once_and_continues_the_chain() {
+    let mut split = sampler(1.0).with_burn_in(300).with_thinning(3);
+    let mut first = split.sample(100);
+    first.values.extend(split.sample(100).values);
+
+    let mut whole = sampler(1.0).with_burn_in(300).with_thinning(3);
+    let all = whole.sample(200);
+
+    assert_eq!(first.values, all.values);
+    assert_eq!(split.state(), whole.state());
+    assert_eq!(split.current_energy(), whole.current_energy());
+}
+
+#[test]
+fn test_autocorrelation_time_of_ar1_process() {
+    // x_t = phi * x_{t-1} + noise has tau = (1 + phi) / (1 - phi)
+    let phi: f64 = 0.8;
+    let mut rng = seeded_rng(SEED);
+    let mut x = 0.0;
+    let values: Vec<f64> = (0..200_000)
+        .map(|_| {
+            x = phi * x + rng.gen_range(-1.0..1.0);
+            x
+        })
+        .collect();
+
+    let expected = (1.0 + phi) / (1.0 - phi);
+    let tau = integrated_autocorrelation_time(&values);
+    assert!(
+        (tau - expected).abs() < 0.1 * expected,
+        "Estimated tau {tau} should be close to {expected}"
+    );
+    assert!((effective_sample_size(&values) - 200_000.0 / tau).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "Temperature must be positive")]
+fn test_sampler_rejects_non_positive_temperature() {
+    sampler(0.0);
+}
@@ -0,0 +1,419 @@
+//! Tests for the cooling schedules.
+//!
+//! These tests verify the temperature sequences produced by the schedules,
+//! including schedules that adapt to feedback reported by the annealer.
+
+mod common;
+
+use common::LineState;
+use frostfire::prelude::*;
+use std::thread;
+use std::time::Duration;
+
+// Seed for reproducibility
+const SEED: u64 = 99;
+
+/// A quadratic energy with its minimum at the origin.
+struct SquareEnergy;
+
+impl Energy for SquareEnergy {
+    type State = LineState;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        state.0 * state.0
+    }
+}
+
+/// Builds feedback for a single iteration with the given acceptance outcome.
+fn feedback(iteration: usize, accepted: bool) -> Feedback {
+    Feedback {
+        iteration,
+        temperature: 1.0,
+        accepted,
+        delta: 0.0,
+        current_energy: 0.0,
+        best_energy: 0.0,
+    }
+}
+
+#[test]
+fn test_adaptive_schedule_reacts_to_feedback() {
+    let mut schedule = AdaptiveSchedule::with_params(100.0, 0.44, 0.9, 0.99);
+
+    // Accepting everything: cool as fast as allowed
+    for i in 0..100 {
+        schedule.observe(&feedback(i, true));
+    }
+    assert!((schedule.next_temp(100.0, 100) - 90.0).abs() < 1e-9);
+
+    // Rejecting everything: cool as slowly as allowed
+    for i in 100..200 {
+        schedule.observe(&feedback(i, false));
+    }
+    assert!((schedule.next_temp(100.0, 200) - 99.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_adaptive_schedule_adapts_inside_annealer() {
+    // A hot start accepts nearly everything, so the adaptive schedule must cool
+    // faster than a geometric schedule using its slowest rate
+    let iterations = 2000;
+    let mut adaptive = Annealer::new(
+        LineState(5.0),
+        SquareEnergy,
+        AdaptiveSchedule::with_params(1000.0, 0.44, 0.9, 0.99),
+        seeded_rng(SEED),
+        iterations,
+    );
+    let adaptive_result = adaptive.run_with_stats();
+
+    let mut geometric = Annealer::new(
+        LineState(5.0),
+        SquareEnergy,
+        GeometricSchedule::new(1000.0, 0.99),
+        seeded_rng(SEED),
+        iterations,
+    );
+    let geometric_result = geometric.run_with_stats();
+
+    println!("Adaptive final temperature: {}", adaptive_result.final_temp);
+    println!(
+        "Geometric final temperature: {}",
+        geometric_result.final_temp
+    );
+
+    assert!(
+        adaptive_result.final_temp < geometric_result.final_temp,
+        "Adaptive schedule did not react to the acceptance rate"
+    );
+    assert!(adaptive_result.best_energy < 0.01);
+}
+
+#[test]
+fn test_budget_schedules_reach_final_temperature() {
+    let budget = 5000;
+    let final_temps = [
+        (
+            "geometric",
+            run_to_budget(GeometricSchedule::with_budget(50.0, 0.05, budget)),
+        ),
+        (
+            "linear",
+            run_to_budget(LinearSchedule::new(50.0, 0.05, budget)),
+        ),
+        (
+            "exponential",
+            run_to_budget(ExponentialSchedule::new(50.0, 0.05, budget)),
+        ),
+        (
+            "cosine",
+            run_to_budget(CosineSchedule::new(50.0, 0.05, budget)),
+        ),
+    ];
+
+    for (name, final_temp) in final_temps {
+        println!("{}: final temperature {}", name, final_temp);
+        assert!(
+            (final_temp - 0.05).abs() < 1e-9,
+            "{} schedule ended at {}",
+            name,
+            final_temp
+        );
+    }
+}
+
+#[test]
+fn test_geometric_budget_too_long_to_represent() {
+    // (0.999999 / 1)^(1 / 2^60) rounds to 1, which would never cool
+    let schedule = GeometricSchedule::with_budget(1.0, 0.999_999, 1 << 60);
+    let next = schedule.next_temp(1.0, 0);
+    assert!(next < 1.0);
+    assert_eq!(next, f64::from_bits(1.0f64.to_bits() - 1));
+}
+
+/// Runs an annealer for 5000 iterations and returns its final temperature.
+fn run_to_budget(schedule: impl Schedule) -> f64 {
+    let mut annealer = Annealer::new(
+        LineState(5.0),
+        SquareEnergy,
+        schedule,
+        seeded_rng(SEED),
+        5000,
+    );
+    annealer.run_with_stats().final_temp
+}
+
+#[test]
+fn test_budget_schedule_shapes() {
+    let linear = LinearSchedule::new(10.0, 1.0, 100);
+    let exponential = ExponentialSchedule::new(10.0, 1.0, 100);
+    let cosine = CosineSchedule::new(10.0, 1.0, 100);
+
+    // Halfway through the budget
+    assert!((linear.next_temp(0.0, 49) - 5.5).abs() < 1e-12);
+    assert!((exponential.next_temp(0.0, 49) - 10.0_f64.sqrt()).abs() < 1e-12);
+    assert!((cosine.next_temp(0.0, 49) - 5.5).abs() < 1e-12);
+
+    // Cosine decays slower than linear early and faster late
+    assert!(cosine.next_temp(0.0, 9) > linear.next_temp(0.0, 9));
+    assert!(cosine.next_temp(0.0, 89) < linear.next_temp(0.0, 89));
+
+    // Every schedule decreases monotonically and holds after the budget
+    for schedule in [&linear as &dyn Schedule, &exponential, &cosine] {
+        let temps: Vec<f64> = (0..150).map(|i| schedule.next_temp(0.0, i)).collect();
+        assert!(temps.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(temps[99..].iter().all(|&t| (t - 1.0).abs() < 1e-12));
+    }
+}
+
+#[test]
+fn test_wall_clock_schedule_follows_elapsed_time() {
+    let schedule = WallClockSchedule::new(10.0, 1.0, Duration::from_millis(300), Decay::Linear);
+
+    // The clock starts at the first call
+    let first = schedule.next_temp(10.0, 0);
+    assert!(first > 9.0, "started at {}", first);
+
+    thread::sleep(Duration::from_millis(30));
+    let middle = schedule.next_temp(first, 1);
+    assert!(middle < first && middle > 1.0);
+
+    // Once the budget is spent the final temperature is held
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(schedule.next_temp(middle, 2), 1.0);
+}
+
+/// Iterates a schedule the way the annealer does and returns the temperatures it uses.
+fn temperatures(schedule: &dyn Schedule, count: usize) -> Vec<f64> {
+    let mut temps = vec![schedule.initial_temp()];
+    for i in 0..count - 1 {
+        let next = schedule.next_temp(temps[i], i);
+        temps.push(next);
+    }
+    temps
+}
+
+#[test]
+fn test_classic_schedules_match_closed_forms() {
+    let lundy_mees = temperatures(&LundyMeesSchedule::new(10.0, 0.01), 200);
+    let cauchy = temperatures(&CauchySchedule::new(10.0), 200);
+    let linear = temperatures(&LinearSchedule::with_step(10.0, 0.1, 2.0), 200);
+    let quadratic = temperatures(&QuadraticSchedule::new(10.0, 0.05), 200);
+    let geometric = temperatures(&GeometricSchedule::new(10.0, 0.95), 200);
+
+    for (k, &t) in lundy_mees.iter().enumerate() {
+        let expected = 10.0 / (1.0 + k as f64 * 0.01 * 10.0);
+        assert!((t - expected).abs() < 1e-9, "Lundy–Mees at {}: {}", k, t);
+    }
+    for (k, &t) in cauchy.iter().enumerate() {
+        assert!((t - 10.0 / (1.0 + k as f64)).abs() < 1e-12);
+    }
+    for (k, &t) in linear.iter().enumerate() {
+        let expected = (10.0 - 0.1 * k as f64).max(2.0);
+        assert!((t - expected).abs() < 1e-9, "linear at {}: {}", k, t);
+    }
+    for (k, &t) in quadratic.iter().enumerate() {
+        let k = k as f64;
+        assert!((t - 10.0 / (1.0 + 0.05 * k * k)).abs() < 1e-12);
+    }
+    for (k, &t) in geometric.iter().enumerate() {
+        assert!((t - 10.0 * 0.95_f64.powi(k as i32)).abs() < 1e-9);
+    }
+}
+
+/// Builds feedback reporting the current energy of an iteration.
+fn energy_feedback(iteration: usize, current_energy: f64) -> Feedback {
+    Feedback {
+        current_energy,
+        ..feedback(iteration, true)
+    }
+}
+
+#[test]
+fn test_huang_schedule_cools_by_energy_deviation() {
+    let mut schedule = HuangSchedule::new(10.0, 0.7).with_window(4);
+
+    // No fluctuation measured yet: the temperature is held
+    assert_eq!(schedule.next_temp(10.0, 0), 10.0);
+    for i in 0..3 {
+        schedule.observe(&energy_feedback(i, 7.0));
+        assert_eq!(schedule.next_temp(10.0, i), 10.0);
+    }
+
+    // Energies alternating between 0 and 40 have a standard deviation of 20
+    for i in 0..8 {
+        schedule.observe(&energy_feedback(i, if i % 2 == 0 { 0.0 } else { 40.0 }));
+    }
+    let expected = 10.0 * (-0.7 * 10.0 / 20.0_f64).exp();
+    assert!((schedule.next_temp(10.0, 8) - expected).abs() < 1e-12);
+
+    // Once the energy settles, the window forgets the old fluctuations and
+    // the temperature halves
+    for i in 8..12 {
+        schedule.observe(&energy_feedback(i, 3.0));
+    }
+    assert_eq!(schedule.next_temp(10.0, 12), 5.0);
+}
+
+#[test]
+fn test_lam_delosme_schedule_tracks_target_acceptance() {
+    let schedule = LamDelosmeSchedule::new(10.0, 1000);
+
+    // The target curve: 1 at the start, 0.44 plateau, 0.44/440 at the end
+    assert!((schedule.target_acceptance(0) - 1.0).abs() < 1e-12);
+    assert!((schedule.target_acceptance(150) - 0.44).abs() < 1e-12);
+    assert_eq!(schedule.target_acceptance(400), 0.44);
+    assert!((schedule.target_acceptance(1000) - 0.001).abs() < 1e-12);
+    let targets: Vec<f64> = (0..=1000).map(|i| schedule.target_acceptance(i)).collect();
+    assert!(targets.windows(2).all(|pair| pair[1] <= pair[0]));
+
+    // Accepting more than the target cools; accepting less heats
+    let mut schedule = schedule;
+    for i in 0..2000 {
+        schedule.observe(&feedback(i, true));
+    }
+    assert!(schedule.acceptance_rate() > 0.98);
+    assert!((schedule.next_temp(10.0, 400) - 9.99).abs() < 1e-12);
+    for i in 0..2000 {
+        schedule.observe(&feedback(i, false));
+    }
+    assert!(schedule.acceptance_rate() < 0.44);
+    assert!((schedule.next_temp(9.99, 400) - 10.0).abs() < 1e-12);
+}
+
+/// A user-defined schedule that lowers the temperature by one each iteration.
+struct CountdownSchedule;
+
+impl Schedule for CountdownSchedule {
+    fn initial_temp(&self) -> f64 {
+        100.0
+    }
+
+    fn next_temp(&self, current_temp: f64, _iteration: usize) -> f64 {
+        current_temp - 1.0
+    }
+}
+
+#[test]
+fn test_combinators_compose_temperature_sequences() {
+    // Plateaus of 4 iterations over a user-defined schedule
+    let plateau = temperatures(&CountdownSchedule.plateau(4), 12);
+    assert_eq!(
+        plateau,
+        [100.0, 100.0, 100.0, 100.0, 99.0, 99.0, 99.0, 99.0, 98.0, 98.0, 98.0, 98.0]
+    );
+
+    // Budget schedules inside a plateau span one level per step
+    let levels = temperatures(&LinearSchedule::new(10.0, 1.0, 9).plateau(2), 20);
+    assert!((levels[2] - 9.0).abs() < 1e-12 && (levels[3] - 9.0).abs() < 1e-12);
+    assert!((levels[19] - 1.0).abs() < 1e-12);
+
+    // The second phase starts at its own initial temperature and iteration zero
+    let piecewise = temperatures(
+        &GeometricSchedule::new(10.0, 0.5).then(LinearSchedule::new(1.0, 0.5, 5), 3),
+        9,
+    );
+    let expected = [10.0, 5.0, 2.5, 1.0, 0.9, 0.8, 0.7, 0.6, 0.5];
+    for (t, e) in piecewise.iter().zip(expected) {
+        assert!((t - e).abs() < 1e-12, "{:?}", piecewise);
+    }
+
+    // Clamping bounds both the initial temperature and the sequence
+    let clamped = temperatures(&CountdownSchedule.clamped(95.0, 98.0), 6);
+    assert_eq!(clamped, [98.0, 97.0, 96.0, 95.0, 95.0, 95.0]);
+
+    // Scaling applies the wrapped schedule on its own temperature scale
+    let scaled = temperatures(&CountdownSchedule.scaled(0.5), 3);
+    assert_eq!(scaled, [50.0, 49.5, 49.0]);
+
+    // Cycles restart from the initial temperature
+    let cycled = temperatures(&CountdownSchedule.cycled(3), 7);
+    assert_eq!(cycled, [100.0, 99.0, 98.0, 100.0, 99.0, 98.0, 100.0]);
+}
+
+#[test]
+fn test_combinators_forward_feedback_and_state() {
+    let phases = || {
+        AdaptiveSchedule::with_params(100.0, 0.44, 0.9, 0.99)
+            .then(AdaptiveSchedule::with_params(100.0, 0.44, 0.9, 0.99), 100)
+            .clamped(1.0, f64::INFINITY)
+    };
+    let fresh = AdaptiveSchedule::with_params(100.0, 0.44, 0.9, 0.99);
+
+    // Feedback from the first phase only reaches the first schedule
+    let mut schedule = phases();
+    for i in 0..100 {
+        schedule.observe(&feedback(i, true));
+    }
+    assert!((schedule.next_temp(100.0, 50) - 90.0).abs() < 1e-9);
+    assert_eq!(schedule.next_temp(100.0, 150), fresh.next_temp(100.0, 50));
+
+    // The saved state of every wrapped schedule is restored
+    let mut saved = Vec::new();
+    schedule.save_state(&mut saved).unwrap();
+    let mut restored = phases();
+    restored.load_state(&mut saved.as_slice()).unwrap();
+    assert_eq!(restored.next_temp(100.0, 50), schedule.next_temp(100.0, 50));
+    assert_eq!(
+        restored.next_temp(100.0, 150),
+        schedule.next_temp(100.0, 150)
+    );
+}
+
+#[test]
+fn test_tabulated_schedule_interpolates_control_points() {
+    let points = vec![(10, 100.0), (20, 1.0), (40, 0.5)];
+    let linear = TabulatedSchedule::new(points.clone(), Interpolation::Linear);
+    let log = TabulatedSchedule::new(points, Interpolation::Log);
+
+    // The first temperature is held before the first control point
+    assert_eq!(linear.initial_temp(), 100.0);
+    assert_eq!(linear.temperature_at(10), 100.0);
+
+    assert!((linear.temperature_at(15) - 50.5).abs() < 1e-12);
+    assert!((log.temperature_at(15) - 10.0).abs() < 1e-12);
+    assert!((linear.temperature_at(30) - 0.75).abs() < 1e-12);
+    assert!((log.temperature_at(30) - 0.5_f64.sqrt()).abs() < 1e-12);
+
+    // The control points are reproduced exactly and the last one is held
+    for schedule in [&linear, &log] {
+        assert_eq!(schedule.temperature_at(20), 1.0);
+        assert_eq!(schedule.temperature_at(40), 0.5);
+        assert_eq!(schedule.temperature_at(1000), 0.5);
+        // next_temp(_, k) is the temperature for iteration k + 1
+        assert_eq!(schedule.next_temp(0.0, 19), 1.0);
+    }
+}
+
+#[test]
+fn test_tabulated_schedule_loads_csv() {
+    let path = std::env::temp_dir().join(format!("frostfire-profile-{}.csv", std::process::id()));
+    std::fs::write(
+        &path,
+        "# tuned on instance 7\niteration,temperature\n0, 8.0\n\n50, 2.0\n100, 0.5\n",
+    )
+    .unwrap();
+    let schedule = TabulatedSchedule::from_csv(&path, Interpolation::Log);
+    std::fs::remove_file(&path).ok();
+
+    let schedule = schedule.expect("failed to load profile");
+    assert_eq!(schedule.points(), [(0, 8.0), (50, 2.0), (100, 0.5)]);
+    assert!((schedule.temperature_at(25) - 4.0).abs() < 1e-12);
+
+    // Malformed rows, including a malformed first row, and invalid profiles are rejected
+    for csv in [
+        "0,8.0\nfifty,2.0\n",
+        "0,8.0\n0,2.0\n",
+        "0,8.0\n10,-1.0\n",
+        "iteration,temperature\n",
+        "0;100.0\n50,2.0\n",
+        "0,hot\n50,2.0\n",
+    ] {
+        let error = TabulatedSchedule::from_reader(csv.as_bytes(), Interpolation::Linear)
+            .expect_err("invalid profile was accepted");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData, "{}", csv);
+    }
+
+    let missing = TabulatedSchedule::from_csv("/nonexistent/profile.csv", Interpolation::Linear);
+    assert_eq!(missing.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
@@ -0,0 +1,92 @@
+//! Tests for driving the annealer one iteration at a time.
+//!
+//! These tests verify that stepping through a run, or mixing steps with
+//! `run_with_stats`, produces exactly the same run as a single call, and that
+//! runs can be ended early by the caller.
+
+mod common;
+
+use common::integer_annealer;
+use frostfire::core::termination::NoImprovement;
+use frostfire::prelude::*;
+
+// Seed for reproducibility
+const SEED: u64 = 777;
+
+#[test]
+fn test_stepping_matches_full_run() {
+    let reference = integer_annealer(SEED, 2000).run_with_stats();
+
+    let mut stepped = integer_annealer(SEED, 2000);
+    let records: Vec<IterationRecord> = stepped.steps().collect();
+    let result = stepped.finish();
+
+    assert_eq!(records.len(), 2000);
+    assert!(records
+        .iter()
+        .enumerate()
+        .all(|(i, record)| record.iteration == i));
+    assert_eq!(
+        records.iter().filter(|record| record.accepted).count(),
+        reference.accepted_moves
+    );
+    assert_eq!(records.last().unwrap().best_energy, reference.best_energy);
+
+    assert_eq!(result.stop_reason, StopReason::MaxIterations);
+    assert_eq!(result.iterations, reference.iterations);
+    assert_eq!(result.final_state, reference.final_state);
+    assert_eq!(result.final_temp, reference.final_temp);
+    assert_eq!(result.best_state, reference.best_state);
+
+    // The run is over: stepping again starts a new run from the final state
+    assert_eq!(stepped.step().map(|record| record.iteration), Some(0));
+}
+
+#[test]
+fn test_steps_then_run_continues_the_same_run() {
+    let reference = integer_annealer(SEED, 2000).run_with_stats();
+
+    let mut mixed = integer_annealer(SEED, 2000);
+    for _ in 0..500 {
+        mixed.step().expect("run ended early");
+    }
+    let result = mixed.run_with_stats();
+
+    assert_eq!(result.iterations, 2000);
+    assert_eq!(result.evaluations, reference.evaluations);
+    assert_eq!(result.accepted_moves, reference.accepted_moves);
+    assert_eq!(result.final_state, reference.final_state);
+    assert_eq!(result.final_temp, reference.final_temp);
+}
+
+#[test]
+fn test_finish_ends_run_early() {
+    let mut annealer = integer_annealer(SEED, 2000);
+
+    // Custom stopping logic in the caller
+    let last = annealer
+        .steps()
+        .take_while(|record| record.current_energy > 10.0)
+        .last()
+        .expect("no iterations performed");
+    let result = annealer.finish();
+
+    assert_eq!(result.stop_reason, StopReason::Interrupted);
+    assert_eq!(result.iterations, last.iteration + 2);
+    assert!(result.final_energy <= 10.0);
+}
+
+#[test]
+fn test_step_stops_on_termination_criterion() {
+    let mut annealer = integer_annealer(SEED, 100_000).with_termination(NoImprovement::new(300));
+
+    let count = annealer.steps().count();
+    assert!(count < 100_000);
+
+    // The run stays over until it is finished
+    assert!(annealer.step().is_none());
+
+    let result = annealer.finish();
+    assert_eq!(result.iterations, count);
+    assert_eq!(result.stop_reason, StopReason::Stagnation);
+}
@@ -0,0 +1,127 @@
+//! Tests for tabu-augmented annealing.
+//!
+//! These tests verify that accepted moves stay tabu for exactly their
+//! tenure, that aspiration admits tabu moves yielding a new best, and that
+//! state attributes prevent revisiting recent states.
+
+mod common;
+
+use common::{AbsEnergy, IntegerState};
+use frostfire::prelude::*;
+use std::sync::{Arc, Mutex};
+
+// Seed for reproducibility
+const SEED: u64 = 31;
+
+/// Every state has the same energy, so every move is accepted.
+struct FlatEnergy;
+
+impl Energy for FlatEnergy {
+    type State = IntegerState;
+
+    fn cost(&self, _state: &Self::State) -> f64 {
+        0.0
+    }
+}
+
+/// Records the states and iterations of accepted moves.
+#[derive(Default)]
+struct AcceptLog(Vec<(usize, i64)>);
+
+impl Observer<IntegerState> for AcceptLog {
+    fn on_accept(&mut self, iteration: usize, state: &IntegerState, _energy: f64) {
+        self.0.push((iteration, state.0));
+    }
+}
+
+#[test]
+fn test_accepted_moves_are_tabu_for_their_tenure() {
+    // Every move shares one attribute, so each acceptance blocks the next 5 proposals
+    let tabu =
+        TabuList::on_moves(5, |_: &IntegerState, _: &IntegerState| ()).with_aspiration(false);
+    let mut annealer = Annealer::new(
+        IntegerState(0),
+        FlatEnergy,
+        GeometricSchedule::new(1.0, 0.99),
+        seeded_rng(SEED),
+        600,
+    )
+    .with_tabu(tabu);
+
+    let records: Vec<IterationRecord> = annealer.steps().collect();
+    let result = annealer.finish();
+
+    assert!(records
+        .iter()
+        .all(|record| record.accepted == record.iteration.is_multiple_of(6)));
+    assert_eq!(result.accepted_moves, 100);
+    assert_eq!(result.tabu_rejections, 500);
+    assert_eq!(result.rejected_moves, 500);
+
+    // Without aspiration, tabu proposals are rejected without being evaluated
+    assert_eq!(result.evaluations, 101);
+}
+
+#[test]
+fn test_aspiration_admits_new_best_moves() {
+    // Everything stays tabu after the first acceptance; at this temperature
+    // only downhill moves are accepted, so every acceptance is a new best
+    let descend = |aspiration: bool| {
+        let tabu = TabuList::on_moves(1_000_000, |_: &IntegerState, _: &IntegerState| ())
+            .with_aspiration(aspiration);
+        Annealer::new(
+            IntegerState(30),
+            AbsEnergy,
+            GeometricSchedule::new(1e-6, 0.999),
+            seeded_rng(SEED),
+            2000,
+        )
+        .with_tabu(tabu)
+        .run_with_stats()
+    };
+
+    let with_aspiration = descend(true);
+    assert_eq!(with_aspiration.best_energy, 0.0);
+    assert_eq!(with_aspiration.accepted_moves, 30);
+    assert!(with_aspiration.tabu_rejections > 0);
+
+    let without_aspiration = descend(false);
+    assert_eq!(without_aspiration.best_energy, 29.0);
+    assert_eq!(without_aspiration.accepted_moves, 1);
+    assert!(without_aspiration.tabu_rejections > 1990);
+}
+
+#[test]
+fn test_state_attributes_prevent_revisits() {
+    let log = Arc::new(Mutex::new(AcceptLog::default()));
+    let tabu = TabuList::on_states(10, |state: &IntegerState| state.0).with_aspiration(false);
+    let mut annealer = Annealer::new(
+        IntegerState(0),
+        FlatEnergy,
+        GeometricSchedule::new(1.0, 0.99),
+        seeded_rng(SEED),
+        3000,
+    )
+    .with_tabu(tabu)
+    .with_observer(log.clone());
+
+    let result = annealer.run_with_stats();
+    assert!(result.tabu_rejections > 0);
+
+    // No state is visited again within 10 iterations of the previous visit
+    let visits = std::mem::take(&mut log.lock().unwrap().0);
+    for (k, &(iteration, state)) in visits.iter().enumerate() {
+        if let Some(&(previous, _)) = visits[..k].iter().rev().find(|(_, s)| *s == state) {
+            assert!(
+                iteration - previous > 10,
+                "{} revisited at {}",
+                state,
+                iteration
+            );
+        }
+    }
+
+    // A new run starts with an empty memory
+    let rerun = annealer.run_with_stats();
+    assert_eq!(rerun.iterations, 3000);
+}
@@ -0,0 +1,86 @@
+//! Tests for the parallel tempering engine.
+//!
+//! These tests verify that replica exchange finds the global minimum of a
+//! rugged landscape, reports sensible swap statistics, and produces identical
+//! results for a given seed regardless of the number of threads.
+
+mod common;
+
+use common::{RastriginEnergy, RastriginPoint};
+use frostfire::prelude::*;
+
+// Seed for reproducibility
+const SEED: u64 = 4242;
+
+/// A geometric temperature ladder between `low` and `high`.
+fn ladder(low: f64, high: f64, count: usize) -> Vec<f64> {
+    let ratio = (high / low).powf(1.0 / (count - 1) as f64);
+    (0..count).map(|k| low * ratio.powi(k as i32)).collect()
+}
+
+fn tempering(threads: usize) -> ParallelTempering<RastriginPoint, RastriginEnergy> {
+    ParallelTempering::new(
+        RastriginPoint {
+            coords: vec![4.5, -4.5, 4.5],
+        },
+        RastriginEnergy,
+        ladder(0.05, 20.0, 8),
+        SEED,
+        300,
+    )
+    .with_steps_per_sweep(50)
+    .with_threads(threads)
+}
+
+#[test]
+fn test_tempering_finds_global_minimum() {
+    let result = tempering(4).run();
+
+    println!("Best energy: {}", result.best_energy);
+    println!("Best state: {:?}", result.best_state.coords);
+    println!("Swap acceptance rates: {:?}", result.swap_acceptance_rates);
+    println!("Acceptance rates: {:?}", result.acceptance_rates);
+
+    assert!(
+        result.best_energy < 0.5,
+        "Failed to find the global basin, got {}",
+        result.best_energy
+    );
+
+    // One swap pair per neighboring temperatures, each tried every other sweep
+    assert_eq!(result.swap_acceptance_rates.len(), 7);
+    assert!(result.swap_attempts.iter().all(|&attempts| attempts == 150));
+    assert!(result
+        .swap_acceptance_rates
+        .iter()
+        .all(|&rate| rate > 0.0 && rate <= 1.0));
+
+    // Hotter replicas accept more moves
+    assert!(result.acceptance_rates[7] > result.acceptance_rates[0]);
+}
+
+#[test]
+fn test_tempering_deterministic_across_threads() {
+    let single = tempering(1).run();
+    let multi = tempering(3).run();
+
+    assert_eq!(single.best_energy, multi.best_energy);
+    assert_eq!(single.best_state, multi.best_state);
+    assert_eq!(single.energies, multi.energies);
+    assert_eq!(single.states, multi.states);
+    assert_eq!(single.swap_acceptance_rates, multi.swap_acceptance_rates);
+
+    // A different seed gives a different run
+    let other = ParallelTempering::new(
+        RastriginPoint {
+            coords: vec![4.5, -4.5, 4.5],
+        },
+        RastriginEnergy,
+        ladder(0.05, 20.0, 8),
+        SEED + 1,
+        300,
+    )
+    .with_steps_per_sweep(50)
+    .run();
+    assert_ne!(single.energies, other.energies);
+}
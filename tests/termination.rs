@@ -0,0 +1,138 @@
+//! Tests for the termination criteria of the annealer.
+//!
+//! These tests verify that each stopping condition ends the run at the right
+//! moment and that the triggering reason is reported in the result.
+
+mod common;
+
+use common::integer_annealer;
+use frostfire::core::termination::{
+    AllOf, EvaluationBudget, FrozenDetection, NoImprovement, TargetEnergy, TemperatureFloor,
+    TimeLimit,
+};
+use frostfire::prelude::*;
+use std::time::Duration;
+
+// Seed for reproducibility
+const SEED: u64 = 2024;
+
+#[test]
+fn test_max_iterations_is_default() {
+    let result = integer_annealer(SEED, 1000).run_with_stats();
+
+    assert_eq!(result.stop_reason, StopReason::MaxIterations);
+    assert_eq!(result.iterations, 1000);
+    assert_eq!(result.evaluations, 1001);
+}
+
+#[test]
+fn test_target_energy_and_budgets() {
+    // Target energy: the origin is reachable long before the iteration limit
+    let result = integer_annealer(SEED, 1_000_000)
+        .with_termination(TargetEnergy::new(0.0))
+        .run_with_stats();
+    assert_eq!(result.stop_reason, StopReason::TargetEnergy);
+    assert_eq!(result.best_energy, 0.0);
+    assert!(result.iterations < 1_000_000);
+
+    // Evaluation budget counts the initial evaluation
+    let result = integer_annealer(SEED, 1_000_000)
+        .with_termination(EvaluationBudget::new(250))
+        .run_with_stats();
+    assert_eq!(result.stop_reason, StopReason::EvaluationBudget);
+    assert_eq!(result.evaluations, 250);
+    assert_eq!(result.iterations, 249);
+
+    // Temperature floor: 10 * 0.999^k < 1 once k > ln(10) / -ln(0.999) ≈ 2301.4
+    let result = integer_annealer(SEED, 1_000_000)
+        .with_termination(TemperatureFloor::new(1.0))
+        .run_with_stats();
+    assert_eq!(result.stop_reason, StopReason::TemperatureFloor);
+    assert_eq!(result.iterations, 2302);
+    assert!(result.final_temp < 1.0);
+
+    // Wall-clock budget
+    let result = integer_annealer(SEED, usize::MAX)
+        .with_termination(TimeLimit::new(Duration::from_millis(50)))
+        .run_with_stats();
+    assert_eq!(result.stop_reason, StopReason::TimeLimit);
+}
+
+#[test]
+fn test_stagnation_and_combinators() {
+    // Stagnation: once at the origin, no further improvement is possible
+    let result = integer_annealer(SEED, 1_000_000)
+        .with_termination(NoImprovement::new(2000))
+        .run_with_stats();
+    assert_eq!(result.stop_reason, StopReason::Stagnation);
+    assert!(result.iterations >= 2000);
+
+    // Any: the first satisfied criterion is reported
+    let result = integer_annealer(SEED, 1_000_000)
+        .with_termination(EvaluationBudget::new(100).or(TargetEnergy::new(0.0)))
+        .run_with_stats();
+    assert_eq!(result.stop_reason, StopReason::EvaluationBudget);
+
+    // All: every criterion must hold at once
+    let result = integer_annealer(SEED, 1_000_000)
+        .with_termination(
+            AllOf::new()
+                .with(TemperatureFloor::new(1.0))
+                .with(TargetEnergy::new(0.0)),
+        )
+        .run_with_stats();
+    assert_eq!(
+        result.stop_reason,
+        StopReason::All(vec![StopReason::TemperatureFloor, StopReason::TargetEnergy])
+    );
+    assert!(result.final_temp < 1.0);
+    assert_eq!(result.best_energy, 0.0);
+}
+
+#[test]
+fn test_frozen_detection_stops_run() {
+    let mut annealer = integer_annealer(SEED, 1_000_000)
+        .with_frozen_detection(FrozenDetection::new(0.02, 5).with_level_length(200));
+    let records: Vec<IterationRecord> = annealer.steps().collect();
+    let result = annealer.finish();
+
+    assert_eq!(result.stop_reason, StopReason::Frozen);
+    let frozen_at = result.frozen_at.expect("no freezing iteration reported");
+    assert_eq!(frozen_at % 200, 0);
+    assert_eq!(result.iterations, frozen_at + 1000);
+
+    // Each of the frozen levels accepted under 2% of its moves, without improving
+    for level in records[frozen_at..].chunks(200) {
+        assert!(level.iter().filter(|record| record.accepted).count() < 4);
+    }
+    assert!(records[frozen_at..]
+        .iter()
+        .all(|record| record.best_energy == result.best_energy));
+
+    // The level before was not frozen
+    let previous = &records[frozen_at - 200..frozen_at];
+    assert!(
+        previous.iter().filter(|record| record.accepted).count() >= 4
+            || previous.first().unwrap().best_energy > result.best_energy
+    );
+}
+
+#[test]
+fn test_frozen_detection_counts_equilibrium_levels() {
+    let result = integer_annealer(SEED, 1_000_000)
+        .with_equilibrium(Equilibrium::accepted_moves(20, 500))
+        .with_frozen_detection(FrozenDetection::new(0.05, 3))
+        .run_with_stats();
+
+    assert_eq!(result.stop_reason, StopReason::Frozen);
+    let levels = &result.levels;
+    let frozen = &levels[levels.len() - 3..];
+    assert!(frozen.iter().all(|level| level.acceptance_ratio() < 0.05));
+
+    // The frozen levels start at the reported iteration
+    let frozen_at: usize = levels[..levels.len() - 3]
+        .iter()
+        .map(|level| level.iterations)
+        .sum();
+    assert_eq!(result.frozen_at, Some(frozen_at));
+}
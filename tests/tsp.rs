@@ -7,6 +7,7 @@
 use frostfire::prelude::*;
 use rand::Rng;
 use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 // Seed for reproducibility
 const SEED: u64 = 42;
@@ -133,6 +134,225 @@ impl Energy for TspEnergy {
     }
 }
 
+/// A TSP state that remembers the positions swapped by the move that produced it.
+#[derive(Clone)]
+struct SwapTspState {
+    tour: Vec<usize>,
+    last_swap: Option<(usize, usize)>,
+}
+
+impl State for SwapTspState {
+    fn neighbor(&self, rng: &mut impl Rng) -> Self {
+        let mut new_tour = self.tour.clone();
+        let idx1 = rng.gen_range(0..new_tour.len());
+        let idx2 = rng.gen_range(0..new_tour.len());
+        new_tour.swap(idx1, idx2);
+
+        Self {
+            tour: new_tour,
+            last_swap: Some((idx1, idx2)),
+        }
+    }
+}
+
+/// TSP energy with an O(1) delta for swap moves.
+struct DeltaTspEnergy {
+    problem: TspProblem,
+}
+
+impl DeltaTspEnergy {
+    /// Sums the lengths of the given tour edges, where edge `k` joins positions `k` and `k + 1`.
+    fn edges_length(&self, tour: &[usize], edges: &[usize]) -> f64 {
+        edges
+            .iter()
+            .map(|&k| self.problem.distance(tour[k], tour[(k + 1) % tour.len()]))
+            .sum()
+    }
+}
+
+impl Energy for DeltaTspEnergy {
+    type State = SwapTspState;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        self.problem.tour_distance(&state.tour)
+    }
+
+    fn delta(
+        &self,
+        state: &mut Self::State,
+        candidate: &mut Self::State,
+        _current_energy: f64,
+    ) -> f64 {
+        let (i, j) = match candidate.last_swap {
+            Some((i, j)) if i != j => (i, j),
+            _ => return 0.0,
+        };
+
+        // Only the edges touching the two swapped positions can change
+        let n = state.tour.len();
+        let mut edges = vec![(i + n - 1) % n, i, (j + n - 1) % n, j];
+        edges.sort_unstable();
+        edges.dedup();
+
+        self.edges_length(&candidate.tour, &edges) - self.edges_length(&state.tour, &edges)
+    }
+}
+
+#[test]
+fn test_tsp_incremental_delta() {
+    // Same instance as the large test, but evaluated with O(1) swap deltas
+    let mut rng = seeded_rng(SEED);
+    let num_cities = 50;
+    let problem = TspProblem::random(num_cities, &mut rng);
+    let energy = DeltaTspEnergy {
+        problem: problem.clone(),
+    };
+
+    let initial_state = SwapTspState {
+        tour: TspState::random(num_cities, &mut rng).tour,
+        last_swap: None,
+    };
+    let initial_energy = energy.cost(&initial_state);
+
+    let schedule = GeometricSchedule::new(1000.0, 0.98);
+    let mut annealer = Annealer::new(initial_state, energy, schedule, seeded_rng(SEED), 50000);
+    let result = annealer.run_with_stats();
+
+    println!("Initial energy: {}", initial_energy);
+    println!("Best energy: {}", result.best_energy);
+
+    // The incrementally tracked energies must agree with a full evaluation
+    let best_cost = problem.tour_distance(&result.best_state.tour);
+    let final_cost = problem.tour_distance(&result.final_state.tour);
+    assert!(
+        (result.best_energy - best_cost).abs() < 1e-6,
+        "Tracked best energy drifted from the full cost"
+    );
+    assert!(
+        (result.final_energy - final_cost).abs() < 1e-6,
+        "Tracked final energy drifted from the full cost"
+    );
+
+    assert!(
+        result.best_energy < 0.4 * initial_energy,
+        "Solution did not improve significantly"
+    );
+}
+
+/// Number of times a `TwoOptTour` has been cloned.
+static TWO_OPT_CLONES: AtomicUsize = AtomicUsize::new(0);
+
+/// A tour mutated in place by 2-opt segment reversals.
+struct TwoOptTour {
+    tour: Vec<usize>,
+}
+
+impl Clone for TwoOptTour {
+    fn clone(&self) -> Self {
+        TWO_OPT_CLONES.fetch_add(1, Ordering::SeqCst);
+        Self {
+            tour: self.tour.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        TWO_OPT_CLONES.fetch_add(1, Ordering::SeqCst);
+        self.tour.clone_from(&source.tour);
+    }
+}
+
+impl MoveState for TwoOptTour {
+    // Reverse the segment of the tour between two positions (inclusive)
+    type Move = (usize, usize);
+
+    fn propose_move(&self, rng: &mut impl Rng) -> Self::Move {
+        // Position 0 is never part of the segment, so its neighbors are well defined
+        let i = rng.gen_range(1..self.tour.len() - 1);
+        let j = rng.gen_range(i + 1..self.tour.len());
+        (i, j)
+    }
+
+    fn apply_move(&mut self, mv: &mut Self::Move) {
+        self.tour[mv.0..=mv.1].reverse();
+    }
+
+    fn undo_move(&mut self, mv: &mut Self::Move) {
+        self.tour[mv.0..=mv.1].reverse();
+    }
+}
+
+/// TSP energy with an O(1) delta for 2-opt moves.
+struct TwoOptEnergy {
+    problem: TspProblem,
+}
+
+impl Energy for TwoOptEnergy {
+    type State = TwoOptTour;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        self.problem.tour_distance(&state.tour)
+    }
+
+    fn delta(&self, state: &mut Self::State, mv: &mut (usize, usize), _current_energy: f64) -> f64 {
+        let tour = &state.tour;
+        let (i, j) = *mv;
+        let a = tour[i - 1];
+        let b = tour[i];
+        let c = tour[j];
+        let d = tour[(j + 1) % tour.len()];
+
+        // Edges (a, b) and (c, d) are replaced by (a, c) and (b, d)
+        self.problem.distance(a, c) + self.problem.distance(b, d)
+            - self.problem.distance(a, b)
+            - self.problem.distance(c, d)
+    }
+}
+
+#[test]
+fn test_tsp_two_opt_in_place() {
+    let mut rng = seeded_rng(SEED);
+    let num_cities = 50;
+    let problem = TspProblem::random(num_cities, &mut rng);
+    let energy = TwoOptEnergy {
+        problem: problem.clone(),
+    };
+
+    let initial_state = TwoOptTour {
+        tour: TspState::random(num_cities, &mut rng).tour,
+    };
+    let initial_energy = energy.cost(&initial_state);
+
+    let schedule = GeometricSchedule::new(1000.0, 0.98);
+    let mut annealer = Annealer::new(initial_state, energy, schedule, seeded_rng(SEED), 50000);
+
+    TWO_OPT_CLONES.store(0, Ordering::SeqCst);
+    let result = annealer.run_with_stats();
+    let clones = TWO_OPT_CLONES.load(Ordering::SeqCst);
+
+    println!("Initial energy: {}", initial_energy);
+    println!("Best energy: {}", result.best_energy);
+    println!("Clones: {} over {} iterations", clones, result.iterations);
+
+    // The state is only copied when a new best is recorded, never per iteration
+    assert!(
+        clones < result.iterations / 100 && clones < result.accepted_moves,
+        "State was cloned {} times",
+        clones
+    );
+
+    let best_cost = problem.tour_distance(&result.best_state.tour);
+    assert!(
+        (result.best_energy - best_cost).abs() < 1e-6,
+        "Tracked best energy drifted from the full cost"
+    );
+
+    // 2-opt is a much stronger neighborhood than random swaps
+    assert!(
+        result.best_energy < 0.3 * initial_energy,
+        "Solution did not improve significantly"
+    );
+}
+
 #[test]
 fn test_tsp_small_known_optimal() {
     // Create a TSP problem with a known optimal solution
@@ -0,0 +1,175 @@
+//! Tests for Wang–Landau density-of-states estimation.
+//!
+//! These tests compare the estimated density of states with exact results for
+//! independent spins and a small Ising model, and check the thermodynamics
+//! derived from it, the energy binning and the CSV export.
+
+mod common;
+
+use common::{exact_log_z, FieldEnergy, Spins};
+use frostfire::prelude::*;
+
+// Seed for reproducibility
+const SEED: u64 = 6626;
+
+/// A ferromagnetic Ising model on an L x L periodic lattice.
+struct IsingEnergy {
+    side: usize,
+}
+
+impl Energy for IsingEnergy {
+    type State = Spins;
+
+    fn cost(&self, state: &Self::State) -> f64 {
+        let spin = |x: usize, y: usize| {
+            if state.0[(y % self.side) * self.side + x % self.side] {
+                1.0
+            } else {
+                -1.0
+            }
+        };
+        let mut energy = 0.0;
+        for y in 0..self.side {
+            for x in 0..self.side {
+                energy -= spin(x, y) * (spin(x + 1, y) + spin(x, y + 1));
+            }
+        }
+        energy
+    }
+}
+
+/// The natural logarithm of the binomial coefficient C(n, k).
+fn log_binomial(n: usize, k: usize) -> f64 {
+    (1..=k).map(|i| ((n - k + i) as f64 / i as f64).ln()).sum()
+}
+
+fn field_density(spins: usize) -> DensityOfStates {
+    WangLandau::new(
+        Spins(vec![false; spins]),
+        FieldEnergy,
+        EnergyBins::discrete(0.0, 1.0, spins + 1),
+        seeded_rng(SEED),
+        50_000_000,
+    )
+    .with_final_modification(1e-5)
+    .run()
+}
+
+#[test]
+fn test_wang_landau_matches_binomial_density() {
+    let dos = field_density(10);
+    assert!(dos.converged);
+    assert!(dos.flat_histograms > 0);
+    assert!(dos.modification <= 1e-5);
+
+    let dos = dos.normalized(10.0 * 2.0_f64.ln());
+    for (k, &log_g) in dos.log_g.iter().enumerate() {
+        let exact = log_binomial(10, k);
+        assert!(
+            (log_g - exact).abs() < 0.1,
+            "ln g({k}) = {log_g}, exact {exact}"
+        );
+    }
+}
+
+#[test]
+fn test_wang_landau_thermodynamics() {
+    let dos = field_density(10).normalized(10.0 * 2.0_f64.ln());
+
+    for temperature in [0.3_f64, 1.0, 5.0] {
+        let weight = (-1.0 / temperature).exp();
+        let log_z = exact_log_z(10, temperature);
+        let exact_mean = 10.0 * weight / (1.0 + weight);
+        let exact_heat = 10.0 * weight / ((1.0 + weight).powi(2) * temperature * temperature);
+
+        assert!((dos.log_partition(temperature) - log_z).abs() < 0.05);
+        assert!((dos.free_energy(temperature) + temperature * log_z).abs() < 0.05 * temperature);
+        assert!((dos.mean_energy(temperature) - exact_mean).abs() < 0.05);
+        assert!((dos.heat_capacity(temperature) - exact_heat).abs() < 0.05);
+
+        let exact_entropy = (exact_mean + temperature * log_z) / temperature;
+        assert!((dos.entropy(temperature) - exact_entropy).abs() < 0.1);
+    }
+}
+
+#[test]
+fn test_wang_landau_ising_skips_unreachable_levels() {
+    // The 4 x 4 Ising model has energies -32, -24, -20, ..., with no states
+    // at -28 or 28, two ground states and 32 states with one flipped spin
+    let mut wang_landau = WangLandau::new(
+        Spins(vec![true; 16]),
+        IsingEnergy { side: 4 },
+        EnergyBins::discrete(-32.0, 4.0, 17),
+        seeded_rng(SEED),
+        50_000_000,
+    )
+    .with_final_modification(1e-5);
+    let dos = wang_landau.run().normalized(16.0 * 2.0_f64.ln());
+
+    assert!(dos.converged);
+    assert_eq!(dos.log_g[1], f64::NEG_INFINITY);
+    assert_eq!(dos.log_g[15], f64::NEG_INFINITY);
+    assert!(
+        (dos.log_g[0] - 2.0_f64.ln()).abs() < 0.15,
+        "ln g(-32) = {}",
+        dos.log_g[0]
+    );
+    assert!(
+        (dos.log_g[2] - 32.0_f64.ln()).abs() < 0.15,
+        "ln g(-24) = {}",
+        dos.log_g[2]
+    );
+    assert!(
+        (dos.log_g[16] - dos.log_g[0]).abs() < 0.15,
+        "g(E) should be symmetric"
+    );
+}
+
+#[test]
+fn test_energy_bins_and_csv_export() {
+    let bins = EnergyBins::uniform(-1.0, 1.0, 4);
+    assert_eq!(bins.count(), 4);
+    assert_eq!(bins.index(-1.0), Some(0));
+    assert_eq!(bins.index(-0.1), Some(1));
+    assert_eq!(bins.index(1.0), Some(3));
+    assert_eq!(bins.index(1.01), None);
+    assert_eq!(bins.index(-1.01), None);
+    assert_eq!(bins.center(0), -0.75);
+
+    let levels = EnergyBins::discrete(0.0, 2.0, 3);
+    assert_eq!(levels.index(2.0), Some(1));
+    assert_eq!(levels.center(2), 4.0);
+
+    let dos = DensityOfStates {
+        bins: levels,
+        log_g: vec![0.0, f64::NEG_INFINITY, 1.5],
+        steps: 0,
+        flat_histograms: 0,
+        modification: 1.0,
+        converged: false,
+    };
+    let mut csv = Vec::new();
+    dos.write_csv(&mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "energy,log_g\n0,0\n4,1.5\n"
+    );
+
+    let path = std::env::temp_dir().join(format!("frostfire_dos_{}.csv", std::process::id()));
+    dos.to_csv(&path).unwrap();
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(written, "energy,log_g\n0,0\n4,1.5\n");
+}
+
+#[test]
+#[should_panic(expected = "Initial energy must lie within the energy bins")]
+fn test_wang_landau_rejects_initial_state_outside_bins() {
+    WangLandau::new(
+        Spins(vec![true; 4]),
+        FieldEnergy,
+        EnergyBins::discrete(0.0, 1.0, 3),
+        seeded_rng(SEED),
+        1000,
+    );
+}